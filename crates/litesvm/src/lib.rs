@@ -1,33 +1,106 @@
+#[allow(deprecated)]
+use solana_sysvar::recent_blockhashes::{IterItem, RecentBlockhashes};
 use {
-    litesvm::LiteSVM as LiteSVMOriginal,
-    pyo3::{exceptions::PyFileNotFoundError, prelude::*},
+    litesvm::{
+        types::{
+            FailedTransactionMetadata as FailedTransactionMetadataOriginal,
+            TransactionMetadata as TransactionMetadataOriginal,
+            TransactionResult as TransactionResultOriginal,
+        },
+        LiteSVM as LiteSVMOriginal,
+    },
+    pyo3::{
+        exceptions::{PyFileNotFoundError, PyValueError},
+        prelude::*,
+    },
+    serde::{Deserialize, Serialize},
     solders_account::Account,
     solders_compute_budget::ComputeBudget,
     solders_hash::Hash as Blockhash,
+    solders_keypair::Keypair,
     solders_primitives::{
         clock::Clock, epoch_rewards::EpochRewards, epoch_schedule::EpochSchedule, rent::Rent,
         slot_history::SlotHistory, stake_history::StakeHistory,
     },
     solders_pubkey::Pubkey,
+    solders_rpc_filter::RpcFilterType,
+    solders_rpc_responses::{
+        GetAccountInfoResp, GetMultipleAccountsResp, GetProgramAccountsResp,
+        GetProgramAccountsWithContextResp,
+    },
+    solders_rpc_responses_common::RpcKeyedAccount,
+    solders_rpc_responses_tx_status::RpcConfirmedTransactionStatusWithSignature,
     solders_signature::Signature,
     solders_traits::to_py_err,
-    solders_traits_core::RichcmpEqualityOnly,
+    solders_traits_core::{
+        py_from_bytes_general_via_bincode, pybytes_general_via_bincode, PyBytesGeneral,
+        PyFromBytesGeneral, RichcmpEqualityOnly,
+    },
     solders_transaction::TransactionType,
+    solders_transaction_confirmation_status::TransactionConfirmationStatus,
+    solders_transaction_error::TransactionErrorType,
+    solders_transaction_status_struct::TransactionStatus,
     std::{
-        collections::{HashMap, HashSet},
+        collections::{BTreeMap, HashMap, HashSet, VecDeque},
         path::PathBuf,
     },
-    transaction_metadata::{SimulateResult, TransactionResult},
+    trace::{Coverage, TraceGuard},
+    transaction_metadata::{
+        AccountDiff, FeeReport, InstructionPrograms, SimulateResult, TransactionResult,
+    },
     {
-        agave_feature_set::FeatureSet as FeatureSetOriginal,
-        solana_account::Account as AccountOriginal, solana_clock::Clock as ClockOriginal,
+        agave_feature_set::{
+            enable_extend_program_checked, raise_cpi_nesting_limit_to_8,
+            FeatureSet as FeatureSetOriginal,
+        },
+        agave_precompiles::get_precompiles,
+        solana_account::{Account as AccountOriginal, AccountSharedData, ReadableAccount},
+        solana_address_lookup_table_interface::{
+            instruction as lookup_table_instruction,
+            state::AddressLookupTable as AddressLookupTableOriginal,
+        },
+        solana_builtins::BUILTINS,
+        solana_clock::Clock as ClockOriginal,
+        solana_compute_budget::compute_budget::ComputeBudget as ComputeBudgetOriginal,
         solana_epoch_rewards::EpochRewards as EpochRewardsOriginal,
         solana_epoch_schedule::EpochSchedule as EpochScheduleOriginal,
-        solana_last_restart_slot::LastRestartSlot, solana_rent::Rent as RentOriginal,
-        solana_slot_hashes::SlotHashes, solana_slot_history::SlotHistory as SlotHistoryOriginal,
-        solana_stake_interface::stake_history::StakeHistory as StakeHistoryOriginal,
+        solana_fee_structure::FeeStructure,
+        solana_hash::Hash as HashOriginal,
+        solana_instruction::error::InstructionError,
+        solana_keypair::Keypair as KeypairOriginal,
+        solana_last_restart_slot::LastRestartSlot,
+        solana_loader_v3_interface::{
+            get_program_data_address, instruction as loader_v3_instruction,
+            state::UpgradeableLoaderState,
+        },
+        solana_message::inline_nonce::is_advance_nonce_instruction_data,
+        solana_native_token::LAMPORTS_PER_SOL,
+        solana_nonce::state::DurableNonce,
+        solana_nonce_account::verify_nonce_account,
+        solana_program_runtime::declare_process_instruction,
+        solana_pubkey::Pubkey as PubkeyOriginal,
+        solana_rent::Rent as RentOriginal,
+        solana_rpc_client_types::filter::RpcFilterType as RpcFilterTypeOriginal,
+        solana_sdk_ids::{bpf_loader_upgradeable, native_loader, stake, system_program, sysvar},
+        solana_sha256_hasher::hashv,
+        solana_signer::Signer,
+        solana_slot_hashes::SlotHashes,
+        solana_slot_history::SlotHistory as SlotHistoryOriginal,
+        solana_stake_interface::{
+            stake_history::{StakeHistory as StakeHistoryOriginal, StakeHistoryEntry},
+            state::StakeStateV2,
+        },
+        solana_system_interface::instruction as system_instruction,
+        solana_transaction::{
+            versioned::VersionedTransaction as VersionedTransactionOriginal,
+            Transaction as TransactionOriginal,
+        },
+        solana_transaction_error::TransactionError as TransactionErrorOriginal,
     },
 };
+mod mock;
+mod token;
+mod trace;
 pub mod transaction_metadata;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -129,178 +202,1794 @@ impl FeatureSet {
     }
 }
 
+/// An opaque copy of a ``LiteSVM`` instance's state,
+/// created by ``LiteSVM.snapshot`` and consumed by ``LiteSVM.restore``.
+#[derive(Clone)]
+#[pyclass(module = "solders.litesvm", subclass)]
+pub struct LiteSVMSnapshot {
+    svm: LiteSVMOriginal,
+    feature_set: FeatureSetOriginal,
+    history: VecDeque<HistoryEntry>,
+    seeded: Option<Seeded>,
+    disabled_builtins: HashSet<PubkeyOriginal>,
+    latest_blockhash: HashOriginal,
+    coverage: Coverage,
+}
+
 #[pyclass(module = "solders.litesvm", subclass)]
-pub struct LiteSVM(LiteSVMOriginal);
+pub struct LiteSVM {
+    svm: LiteSVMOriginal,
+    feature_set: FeatureSetOriginal,
+    account_diffs: bool,
+    auto_advance: Option<AutoAdvance>,
+    callbacks: Callbacks,
+    history: VecDeque<HistoryEntry>,
+    mock_programs: HashMap<PubkeyOriginal, Py<PyAny>>,
+    seeded: Option<Seeded>,
+    /// Builtins removed by ``set_feature_set``, which stay in LiteSVM's program cache.
+    disabled_builtins: HashSet<PubkeyOriginal>,
+    /// The address of LiteSVM's own airdrop keypair, if it was funded.
+    airdrop_pubkey: Option<PubkeyOriginal>,
+    /// The latest blockhash. LiteSVM can only change its own by expiring it,
+    /// so its blockhash check is disabled and transactions are checked against this instead.
+    latest_blockhash: HashOriginal,
+    blockhash_check: bool,
+    /// Whether the BPF loaders are replaced with ``trace::TracingLoader``.
+    tracing: bool,
+    coverage: Coverage,
+}
+
+/// The keys of an instance created with a ``seed``.
+struct Seeded {
+    seed: u64,
+    /// Funds airdrops in place of LiteSVM's randomly generated airdrop keypair.
+    faucet: KeypairOriginal,
+    /// The index of the next keypair ``new_keypair`` returns.
+    next_key: u64,
+}
+
+impl Clone for Seeded {
+    fn clone(&self) -> Self {
+        Self {
+            seed: self.seed,
+            faucet: self.faucet.insecure_clone(),
+            next_key: self.next_key,
+        }
+    }
+}
+
+impl Seeded {
+    fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            faucet: derive_keypair(seed, b"faucet"),
+            next_key: 0,
+        }
+    }
+
+    fn next_keypair(&mut self) -> KeypairOriginal {
+        let keypair = derive_keypair(self.seed, &self.next_key.to_le_bytes());
+        self.next_key += 1;
+        keypair
+    }
+}
+
+fn seed_hash(seed: u64, label: &[u8]) -> HashOriginal {
+    hashv(&[b"solders-litesvm", &seed.to_le_bytes(), label])
+}
+
+fn derive_keypair(seed: u64, label: &[u8]) -> KeypairOriginal {
+    KeypairOriginal::new_from_array(seed_hash(seed, label).to_bytes())
+}
+
+/// Where and when a transaction in the transaction history landed, oldest first.
+#[derive(Debug, Clone)]
+struct HistoryEntry {
+    signature: Signature,
+    slot: u64,
+    block_time: i64,
+    addresses: Vec<PubkeyOriginal>,
+}
+
+const MAX_SIGNATURES_FOR_ADDRESS: usize = 1000;
+
+/// Keeps each extend transaction of ``create_lookup_table`` under the packet size limit.
+const LOOKUP_TABLE_ADDRESSES_PER_EXTEND: usize = 20;
+
+/// A transaction's result and the new state of each account it changed.
+type ProcessedTransaction = (TransactionResult, Vec<(PubkeyOriginal, AccountOriginal)>);
+
+/// Python callables registered with ``on_transaction`` and ``on_account_change``.
+#[derive(Default)]
+struct Callbacks {
+    transaction: Vec<Py<PyAny>>,
+    account: Vec<(PubkeyOriginal, Py<PyAny>)>,
+}
+
+const DEFAULT_SLOT_DURATION_MS: u64 = 400;
+
+/// Settings for advancing the slot after each sent transaction.
+#[derive(Debug, Clone, Copy)]
+struct AutoAdvance {
+    slot_duration_ms: u64,
+    /// Milliseconds not yet added to the clock's whole-second timestamp.
+    pending_ms: u64,
+}
+
+/// A recorded RPC response that ``LiteSVM.add_accounts_from_rpc_response`` can install.
+#[derive(FromPyObject)]
+pub enum AccountsResp {
+    AccountInfo(GetAccountInfoResp),
+    MultipleAccounts(GetMultipleAccountsResp),
+    ProgramAccounts(GetProgramAccountsResp),
+    ProgramAccountsWithContext(GetProgramAccountsWithContextResp),
+}
+
+/// The on-disk format written by ``LiteSVM.save``.
+#[derive(Serialize, Deserialize)]
+struct SavedState {
+    accounts: Vec<(Pubkey, Account)>,
+    active_features: HashMap<Pubkey, u64>,
+    inactive_features: HashSet<Pubkey>,
+    blockhash: Blockhash,
+    seed: Option<u64>,
+    next_key: u64,
+    /// The balance of LiteSVM's own airdrop keypair, which can't be saved with its account.
+    airdrop_lamports: Option<u64>,
+}
+
+pybytes_general_via_bincode!(SavedState);
+py_from_bytes_general_via_bincode!(SavedState);
+
+fn split_keyed_accounts(
+    keyed: Vec<RpcKeyedAccount>,
+) -> (Option<Vec<Pubkey>>, Vec<Option<Account>>) {
+    let (pubkeys, accounts) = keyed
+        .into_iter()
+        .map(|x| (x.pubkey, Some(x.account)))
+        .unzip();
+    (Some(pubkeys), accounts)
+}
+
+// The program cache has no way to remove an entry, so a disabled builtin's entry
+// is replaced with one that fails the way a missing entry does.
+declare_process_instruction!(DisabledBuiltin, 0, |_invoke_context| {
+    Err(InstructionError::UnsupportedProgramId)
+});
+
+const AIRDROP_LAMPORTS: u64 = 1_000_000u64.wrapping_mul(LAMPORTS_PER_SOL);
+
+/// Funds LiteSVM's own airdrop keypair, which is random and private,
+/// and returns the address of the account this creates.
+fn fund_airdrop_keypair(svm: &mut LiteSVMOriginal, lamports: u64) -> Option<PubkeyOriginal> {
+    let existing: HashSet<PubkeyOriginal> = svm.accounts_db().inner.keys().copied().collect();
+    svm.set_lamports(lamports);
+    svm.accounts_db()
+        .inner
+        .keys()
+        .find(|pubkey| !existing.contains(pubkey))
+        .copied()
+}
+
+fn new_without_lamports(feature_set: FeatureSetOriginal) -> LiteSVMOriginal {
+    LiteSVMOriginal::default()
+        .with_feature_set(feature_set)
+        .with_builtins()
+        .with_sysvars()
+        .with_precompiles()
+        .with_default_programs()
+        .with_sigverify(true)
+}
+
+impl LiteSVM {
+    fn from_parts(svm: LiteSVMOriginal, feature_set: FeatureSetOriginal) -> Self {
+        Self {
+            latest_blockhash: svm.latest_blockhash(),
+            svm,
+            feature_set,
+            account_diffs: false,
+            auto_advance: None,
+            callbacks: Callbacks::default(),
+            history: VecDeque::new(),
+            mock_programs: HashMap::new(),
+            seeded: None,
+            disabled_builtins: HashSet::new(),
+            airdrop_pubkey: None,
+            blockhash_check: true,
+            tracing: false,
+            coverage: Coverage::new(),
+        }
+    }
+
+    /// A fresh address: derived from the seed if there is one, otherwise ``Pubkey::new_unique``.
+    fn unique_pubkey(&mut self) -> PubkeyOriginal {
+        self.seeded
+            .as_mut()
+            .map_or_else(PubkeyOriginal::new_unique, |seeded| {
+                seeded.next_keypair().pubkey()
+            })
+    }
+
+    /// Replaces the latest blockhash and the ``RecentBlockhashes`` sysvar that holds it.
+    fn set_latest_blockhash(&mut self, blockhash: HashOriginal) {
+        self.latest_blockhash = blockhash;
+        #[allow(deprecated)]
+        self.svm.set_sysvar(&RecentBlockhashes::from_iter([IterItem(
+            0,
+            &blockhash,
+            FeeStructure::default().lamports_per_signature,
+        )]));
+    }
+
+    /// Rejects ``tx`` the way LiteSVM's blockhash check does, unless the check is disabled:
+    /// its recent blockhash must be the latest blockhash or an advanceable durable nonce.
+    #[allow(clippy::result_large_err)]
+    fn check_blockhash(
+        &self,
+        tx: &VersionedTransactionOriginal,
+    ) -> Result<(), FailedTransactionMetadataOriginal> {
+        let recent_blockhash = tx.message.recent_blockhash();
+        if !self.blockhash_check
+            || *recent_blockhash == self.latest_blockhash
+            || (*recent_blockhash
+                != *DurableNonce::from_blockhash(&self.latest_blockhash).as_hash()
+                && self.has_valid_nonce(tx))
+        {
+            return Ok(());
+        }
+        Err(FailedTransactionMetadataOriginal {
+            err: TransactionErrorOriginal::BlockhashNotFound,
+            meta: TransactionMetadataOriginal::default(),
+        })
+    }
+
+    /// Whether ``tx`` starts by advancing a nonce account that holds its recent blockhash,
+    /// signed by the nonce authority. The nonce account must be one of the static keys.
+    fn has_valid_nonce(&self, tx: &VersionedTransactionOriginal) -> bool {
+        let msg = &tx.message;
+        let keys = msg.static_account_keys();
+        let Some(ix) = msg.instructions().first() else {
+            return false;
+        };
+        if keys.get(usize::from(ix.program_id_index)) != Some(&system_program::id())
+            || !is_advance_nonce_instruction_data(&ix.data)
+        {
+            return false;
+        }
+        let Some(nonce_index) = ix.accounts.first().map(|idx| usize::from(*idx)) else {
+            return false;
+        };
+        let nonce_data = keys
+            .get(nonce_index)
+            .filter(|_| msg.is_maybe_writable(nonce_index, None))
+            .and_then(|nonce_address| self.svm.get_account(nonce_address))
+            .and_then(|nonce_account| {
+                verify_nonce_account(&nonce_account.into(), msg.recent_blockhash())
+            });
+        nonce_data.is_some_and(|nonce_data| {
+            ix.accounts.iter().any(|idx| {
+                let idx = usize::from(*idx);
+                msg.is_signer(idx) && keys.get(idx) == Some(&nonce_data.authority)
+            })
+        })
+    }
+
+    /// Removes the builtins and precompiles whose enabling feature is inactive,
+    /// which ``set_builtins`` and ``set_precompiles`` leave in place.
+    /// Programs and mocks installed over them are kept.
+    fn remove_disabled_programs(&mut self) -> PyResult<()> {
+        let is_disabled = |feature_id: Option<PubkeyOriginal>| {
+            feature_id.is_some_and(|feature_id| !self.feature_set.is_active(&feature_id))
+        };
+        let builtins: Vec<_> = BUILTINS
+            .iter()
+            .filter(|builtin| is_disabled(builtin.enable_feature_id))
+            .map(|builtin| (builtin.program_id, true))
+            .collect();
+        let precompiles: Vec<_> = get_precompiles()
+            .iter()
+            .filter(|precompile| is_disabled(precompile.feature))
+            .map(|precompile| (precompile.program_id, false))
+            .collect();
+        self.disabled_builtins
+            .retain(|program_id| builtins.iter().any(|(disabled, _)| disabled == program_id));
+        for (program_id, is_builtin) in builtins.into_iter().chain(precompiles) {
+            let is_native = self
+                .svm
+                .get_account(&program_id)
+                .is_some_and(|acc| acc.owner == native_loader::id());
+            if !is_native || self.mock_programs.contains_key(&program_id) {
+                continue;
+            }
+            if is_builtin {
+                self.svm.add_builtin(program_id, DisabledBuiltin::vm);
+                self.disabled_builtins.insert(program_id);
+            }
+            self.svm
+                .set_account(program_id, AccountOriginal::default())
+                .map_err(to_py_err)?;
+        }
+        Ok(())
+    }
+
+    /// Adds a placeholder account at each disabled builtin that ``tx`` loads only as a program.
+    /// LiteSVM assumes that every program in its cache has an account, so without one it
+    /// would panic instead of failing the transaction as it does for a missing program.
+    fn add_stand_ins(&mut self, tx: &VersionedTransactionOriginal) -> Vec<PubkeyOriginal> {
+        if self.disabled_builtins.is_empty() {
+            return Vec::new();
+        }
+        let msg = &tx.message;
+        let instruction_accounts: HashSet<u8> = msg
+            .instructions()
+            .iter()
+            .flat_map(|ix| ix.accounts.iter().copied())
+            .collect();
+        let stand_ins: Vec<_> = msg
+            .static_account_keys()
+            .iter()
+            .enumerate()
+            .filter(|(idx, pubkey)| {
+                self.disabled_builtins.contains(pubkey)
+                    && !msg.is_maybe_writable(*idx, None)
+                    && u8::try_from(*idx).is_ok_and(|idx| !instruction_accounts.contains(&idx))
+                    && self.svm.get_account(pubkey).is_none()
+            })
+            .map(|(_, pubkey)| *pubkey)
+            .collect();
+        for pubkey in &stand_ins {
+            // A non-executable account, like the default account LiteSVM uses for missing ones.
+            let _ = self.svm.set_account(
+                *pubkey,
+                AccountOriginal {
+                    lamports: 1,
+                    ..Default::default()
+                },
+            );
+        }
+        stand_ins
+    }
+
+    fn remove_stand_ins(&mut self, stand_ins: Vec<PubkeyOriginal>) {
+        for pubkey in stand_ins {
+            let _ = self.svm.set_account(pubkey, AccountOriginal::default());
+        }
+    }
+
+    /// Indexes a sent transaction if it was added to the transaction history.
+    fn record_history(&mut self, res: &TransactionResultOriginal, addresses: Vec<PubkeyOriginal>) {
+        let (signature, err) = match res {
+            Ok(meta) => (meta.signature, None),
+            Err(failed) => (failed.meta.signature, Some(&failed.err)),
+        };
+        // A duplicate leaves the original transaction in the history.
+        if err == Some(&TransactionErrorOriginal::AlreadyProcessed)
+            || self.svm.get_transaction(&signature).is_none()
+        {
+            return;
+        }
+        let clock = self.svm.get_sysvar::<ClockOriginal>();
+        self.history.push_back(HistoryEntry {
+            signature: Signature(signature),
+            slot: clock.slot,
+            block_time: clock.unix_timestamp,
+            addresses,
+        });
+        // Drop the entries the capped transaction history has evicted.
+        while self
+            .history
+            .front()
+            .is_some_and(|entry| self.svm.get_transaction(&entry.signature.0).is_none())
+        {
+            self.history.pop_front();
+        }
+    }
+
+    /// The error of a transaction in the transaction history, if it failed.
+    fn history_err(&self, signature: &Signature) -> Option<TransactionErrorType> {
+        match self.svm.get_transaction(&signature.0)? {
+            Ok(_) => None,
+            Err(failed) => Some(failed.err.clone().into()),
+        }
+    }
+
+    fn history_entries(&self) -> impl DoubleEndedIterator<Item = &HistoryEntry> {
+        self.history
+            .iter()
+            .filter(|entry| self.svm.get_transaction(&entry.signature.0).is_some())
+    }
+
+    /// Sends ``tx`` and returns the result with the writable accounts it changed.
+    fn process_transaction(&mut self, tx: VersionedTransactionOriginal) -> ProcessedTransaction {
+        let track_changes = self.account_diffs
+            || !self.callbacks.transaction.is_empty()
+            || !self.callbacks.account.is_empty();
+        let pre_accounts = track_changes.then(|| self.writable_accounts(&tx));
+        let addresses = self.transaction_addresses(&tx);
+        let instruction_programs = InstructionPrograms::new(&tx.message);
+        let fees = FeeReport::new(&tx.message, &self.feature_set);
+        let traced = self
+            .tracing
+            .then(|| trace::load_traced_programs(&self.svm, &self.feature_set, &addresses));
+        let stand_ins = self.add_stand_ins(&tx);
+        let (raw_res, traces) = {
+            let _mocks = mock::activate(&self.mock_programs);
+            let tracer = traced.as_ref().map(trace::activate);
+            let raw_res = self
+                .check_blockhash(&tx)
+                .and_then(|()| self.svm.send_transaction(tx));
+            (raw_res, tracer.map(TraceGuard::finish))
+        };
+        self.remove_stand_ins(stand_ins);
+        self.record_history(&raw_res, addresses);
+        if let (Some(programs), Some(traces)) = (&traced, &traces) {
+            trace::record_coverage(&mut self.coverage, programs, traces);
+        }
+        let fees = fees.map(|fees| {
+            let payer = self.svm.get_account(&fees.payer.0);
+            self.with_payer(fees, payer)
+        });
+        let mut res = TransactionResult::from(raw_res)
+            .with_instruction_programs(instruction_programs)
+            .with_fee_report(fees)
+            .with_traces(traces);
+        let mut changed = Vec::new();
+        if let Some(pre_accounts) = pre_accounts {
+            let mut diffs = Vec::with_capacity(pre_accounts.len());
+            for (pubkey, pre) in &pre_accounts {
+                let post = self.svm.get_account(pubkey).unwrap_or_default();
+                diffs.push(AccountDiff::new(*pubkey, pre, &post));
+                if post != *pre {
+                    changed.push((*pubkey, post));
+                }
+            }
+            if self.account_diffs {
+                res = res.with_account_diffs(diffs);
+            }
+        }
+        (res, changed)
+    }
+
+    /// Processes ``txs`` in order as one slot's worth of transactions, returning each result
+    /// and whether the batch was kept. A failed atomic batch is rolled back.
+    fn process_batch(
+        &mut self,
+        txs: Vec<VersionedTransactionOriginal>,
+        atomic: bool,
+    ) -> (Vec<ProcessedTransaction>, bool) {
+        let checkpoint = atomic.then(|| self.snapshot());
+        let mut processed = Vec::with_capacity(txs.len());
+        for tx in txs {
+            let (res, changed) = self.process_transaction(tx);
+            let failed = matches!(res, TransactionResult::Err(_));
+            processed.push((res, changed));
+            if let Some(checkpoint) = checkpoint.as_ref().filter(|_| failed) {
+                self.restore(checkpoint);
+                return (processed, false);
+            }
+        }
+        self.advance_slot();
+        (processed, true)
+    }
+
+    /// Fills in the fee payer's balance and rent-exempt minimum after a transaction.
+    fn with_payer(&self, fees: FeeReport, payer: Option<AccountOriginal>) -> FeeReport {
+        let (balance, data_len) = payer.map_or((0, 0), |acc| (acc.lamports, acc.data.len()));
+        fees.with_payer(
+            balance,
+            self.svm.minimum_balance_for_rent_exemption(data_len),
+        )
+    }
+
+    /// Runs the registered callbacks for each processed transaction, in order.
+    fn run_callbacks(slf: &Bound<'_, Self>, processed: &[ProcessedTransaction]) -> PyResult<()> {
+        let py = slf.py();
+        for (res, changed) in processed {
+            // Release the borrow before calling back into Python,
+            // so that callbacks can inspect the LiteSVM instance.
+            let (transaction_callbacks, account_callbacks) = {
+                let this = slf.borrow();
+                let transaction: Vec<_> = this
+                    .callbacks
+                    .transaction
+                    .iter()
+                    .map(|callback| callback.clone_ref(py))
+                    .collect();
+                let account: Vec<_> = this
+                    .callbacks
+                    .account
+                    .iter()
+                    .filter_map(|(pubkey, callback)| {
+                        let (_, post) = changed.iter().find(|(changed, _)| changed == pubkey)?;
+                        Some((*pubkey, post.clone(), callback.clone_ref(py)))
+                    })
+                    .collect();
+                (transaction, account)
+            };
+            if !transaction_callbacks.is_empty() {
+                let changed: Vec<(Pubkey, Account)> = changed
+                    .iter()
+                    .map(|(pubkey, acc)| (Pubkey(*pubkey), Account::from(acc.clone())))
+                    .collect();
+                for callback in transaction_callbacks {
+                    callback.call1(py, (res.clone(), changed.clone()))?;
+                }
+            }
+            for (pubkey, post, callback) in account_callbacks {
+                callback.call1(py, (Pubkey(pubkey), Account::from(post)))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn matching_accounts(
+        &self,
+        predicate: impl Fn(&AccountSharedData) -> bool,
+    ) -> Vec<(Pubkey, Account)> {
+        let mut accounts: Vec<(Pubkey, Account)> = self
+            .svm
+            .accounts_db()
+            .inner
+            .iter()
+            .filter(|(_, acc)| predicate(acc))
+            .map(|(pubkey, acc)| {
+                (
+                    Pubkey(*pubkey),
+                    Account::from(AccountOriginal::from(acc.clone())),
+                )
+            })
+            .collect();
+        accounts.sort_by_key(|(pubkey, _)| *pubkey);
+        accounts
+    }
+
+    /// Moves to the next slot as if the current one had been produced.
+    fn advance_slot(&mut self) {
+        let Some(auto_advance) = self.auto_advance.as_mut() else {
+            return;
+        };
+        auto_advance.pending_ms += auto_advance.slot_duration_ms;
+        let elapsed_secs = auto_advance.pending_ms / 1000;
+        auto_advance.pending_ms %= 1000;
+        let mut clock = self.svm.get_sysvar::<ClockOriginal>();
+        let parent_slot = clock.slot;
+        let schedule = self.svm.get_sysvar::<EpochScheduleOriginal>();
+        clock.slot += 1;
+        clock.unix_timestamp = clock
+            .unix_timestamp
+            .saturating_add(i64::try_from(elapsed_secs).unwrap_or(i64::MAX));
+        let epoch = schedule.get_epoch(clock.slot);
+        if epoch != clock.epoch {
+            clock.epoch = epoch;
+            clock.epoch_start_timestamp = clock.unix_timestamp;
+        }
+        clock.leader_schedule_epoch = schedule.get_leader_schedule_epoch(clock.slot);
+        self.record_slot_transition(parent_slot, clock.slot);
+        self.svm.set_sysvar(&clock);
+        self.expire_blockhash();
+    }
+
+    /// Adds ``parent_slot`` to ``SlotHashes`` and ``slot`` to ``SlotHistory``,
+    /// as a cluster does when it creates the bank for ``slot``.
+    fn record_slot_transition(&mut self, parent_slot: u64, slot: u64) {
+        let mut slot_hashes = self.svm.get_sysvar::<SlotHashes>();
+        slot_hashes.add(parent_slot, self.latest_blockhash);
+        let mut slot_history = self.svm.get_sysvar::<SlotHistoryOriginal>();
+        slot_history.add(slot);
+        self.svm.set_sysvar(&slot_hashes);
+        self.svm.set_sysvar(&slot_history);
+    }
+
+    /// Every account a transaction references, including those loaded from lookup tables.
+    fn transaction_addresses(&self, tx: &VersionedTransactionOriginal) -> Vec<PubkeyOriginal> {
+        let mut addresses = tx.message.static_account_keys().to_vec();
+        for lookup in tx.message.address_table_lookups().unwrap_or_default() {
+            let Some(table_account) = self.svm.get_account(&lookup.account_key) else {
+                continue;
+            };
+            if let Ok(table) = AddressLookupTableOriginal::deserialize(&table_account.data) {
+                addresses.extend(
+                    lookup
+                        .writable_indexes
+                        .iter()
+                        .chain(&lookup.readonly_indexes)
+                        .filter_map(|idx| table.addresses.get(usize::from(*idx)).copied()),
+                );
+            }
+        }
+        addresses
+    }
+
+    /// The writable accounts of a transaction, including those loaded from lookup tables,
+    /// paired with their current state.
+    fn writable_accounts(
+        &self,
+        tx: &VersionedTransactionOriginal,
+    ) -> Vec<(PubkeyOriginal, AccountOriginal)> {
+        let msg = &tx.message;
+        let mut keys: Vec<PubkeyOriginal> = msg
+            .static_account_keys()
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| msg.is_maybe_writable(*idx, None))
+            .map(|(_, pubkey)| *pubkey)
+            .collect();
+        for lookup in msg.address_table_lookups().unwrap_or_default() {
+            let Some(table_account) = self.svm.get_account(&lookup.account_key) else {
+                continue;
+            };
+            if let Ok(table) = AddressLookupTableOriginal::deserialize(&table_account.data) {
+                keys.extend(
+                    lookup
+                        .writable_indexes
+                        .iter()
+                        .filter_map(|idx| table.addresses.get(usize::from(*idx)).copied()),
+                );
+            }
+        }
+        keys.into_iter()
+            .map(|pubkey| (pubkey, self.svm.get_account(&pubkey).unwrap_or_default()))
+            .collect()
+    }
+}
 
 #[pymethods]
 impl LiteSVM {
     #[allow(clippy::new_without_default)]
     #[new]
-    pub fn new() -> Self {
-        Self(LiteSVMOriginal::new())
+    #[pyo3(signature = (feature_set=None, seed=None))]
+    pub fn new(feature_set: Option<&FeatureSet>, seed: Option<u64>) -> PyResult<Self> {
+        let feature_set = feature_set.map_or_else(FeatureSetOriginal::all_enabled, |x| x.0.clone());
+        let mut res = Self::from_parts(new_without_lamports(feature_set.clone()), feature_set);
+        let Some(seed) = seed else {
+            res.airdrop_pubkey = fund_airdrop_keypair(&mut res.svm, AIRDROP_LAMPORTS);
+            return Ok(res);
+        };
+        // LiteSVM's own airdrop keypair is random, so a seeded instance never funds it.
+        res.set_latest_blockhash(seed_hash(seed, b"genesis"));
+        res.seeded = Some(Seeded::new(seed));
+        res.set_lamports(AIRDROP_LAMPORTS)?;
+        Ok(res)
     }
 
     #[staticmethod]
     #[pyo3(name = "default")]
     pub fn new_default() -> Self {
-        Self(LiteSVMOriginal::default())
+        let mut res = Self::from_parts(LiteSVMOriginal::default(), FeatureSetOriginal::default());
+        // LiteSVM's default instance doesn't check blockhashes either.
+        res.blockhash_check = false;
+        res
+    }
+
+    /// Writes every account, including programs and sysvars, the feature set,
+    /// the latest blockhash and the seed to a file.
+    pub fn save(&self, path: PathBuf) -> PyResult<()> {
+        let airdrop_pubkey = self.airdrop_pubkey.map(Pubkey);
+        let state = SavedState {
+            accounts: self
+                .accounts()
+                .into_iter()
+                .filter(|(pubkey, _)| Some(*pubkey) != airdrop_pubkey)
+                .collect(),
+            active_features: FeatureSet(self.feature_set.clone()).active(),
+            inactive_features: FeatureSet(self.feature_set.clone()).inactive(),
+            blockhash: self.latest_blockhash(),
+            seed: self.seeded.as_ref().map(|seeded| seeded.seed),
+            next_key: self.seeded.as_ref().map_or(0, |seeded| seeded.next_key),
+            airdrop_lamports: airdrop_pubkey.map(|pubkey| self.get_balance(pubkey).unwrap_or(0)),
+        };
+        std::fs::write(path, state.pybytes_general())?;
+        Ok(())
+    }
+
+    /// Creates a new instance from a file written by ``save``.
+    #[staticmethod]
+    pub fn load(path: PathBuf) -> PyResult<Self> {
+        let bytes = std::fs::read(path)?;
+        let state = SavedState::py_from_bytes_general(&bytes)?;
+        let feature_set = FeatureSet::new(state.active_features, state.inactive_features).0;
+        let mut res = Self::from_parts(new_without_lamports(feature_set.clone()), feature_set);
+        // Sysvars go first so that programs are loaded at the saved slot,
+        // and ProgramData accounts go before the programs that point to them.
+        let mut accounts = state.accounts;
+        accounts.sort_by_key(|(_, acc)| (acc.owner.0 != sysvar::id(), acc.executable));
+        for (pubkey, acc) in accounts {
+            res.svm
+                .set_account(pubkey.0, acc.into())
+                .map_err(to_py_err)?;
+        }
+        if let Some(lamports) = state.airdrop_lamports {
+            res.airdrop_pubkey = fund_airdrop_keypair(&mut res.svm, lamports);
+        }
+        res.seeded = state.seed.map(|seed| Seeded {
+            next_key: state.next_key,
+            ..Seeded::new(seed)
+        });
+        // The saved RecentBlockhashes sysvar already holds the blockhash.
+        res.latest_blockhash = state.blockhash.0;
+        Ok(res)
+    }
+
+    /// Adds the account in a ``solana account --output json`` dump and returns its address.
+    pub fn load_account_from_json(&mut self, path: PathBuf) -> PyResult<Pubkey> {
+        let raw = std::fs::read_to_string(path)?;
+        let keyed: RpcKeyedAccount = serde_json::from_str(&raw).map_err(to_py_err)?;
+        self.set_account(keyed.pubkey, &keyed.account)?;
+        Ok(keyed.pubkey)
+    }
+
+    /// Adds every ``.json`` account dump in a directory, as ``solana-test-validator --account-dir`` does.
+    pub fn load_accounts_from_dir(&mut self, path: PathBuf) -> PyResult<Vec<Pubkey>> {
+        let mut keyed_accounts = Vec::new();
+        for entry in std::fs::read_dir(path)? {
+            let file_path = entry?.path();
+            if file_path.extension().is_some_and(|ext| ext == "json") {
+                let raw = std::fs::read_to_string(file_path)?;
+                let keyed: RpcKeyedAccount = serde_json::from_str(&raw).map_err(to_py_err)?;
+                keyed_accounts.push(keyed);
+            }
+        }
+        // ProgramData accounts must exist before the programs that point to them.
+        keyed_accounts.sort_by_key(|x| (x.account.executable, x.pubkey));
+        for keyed in &keyed_accounts {
+            self.set_account(keyed.pubkey, &keyed.account)?;
+        }
+        Ok(keyed_accounts.into_iter().map(|x| x.pubkey).collect())
+    }
+
+    /// Writes an account in the ``solana account --output json`` format.
+    pub fn save_account_to_json(&self, pubkey: Pubkey, path: PathBuf) -> PyResult<()> {
+        let account = self
+            .get_account(pubkey)
+            .ok_or_else(|| PyValueError::new_err(format!("Account {pubkey} not found")))?;
+        let keyed = RpcKeyedAccount { pubkey, account };
+        let raw = serde_json::to_string_pretty(&keyed).map_err(to_py_err)?;
+        std::fs::write(path, raw)?;
+        Ok(())
+    }
+
+    /// Writes each account to ``<pubkey>.json`` in a directory, in the format read by ``load_accounts_from_dir``.
+    pub fn save_accounts_to_dir(&self, pubkeys: Vec<Pubkey>, path: PathBuf) -> PyResult<()> {
+        std::fs::create_dir_all(&path)?;
+        for pubkey in pubkeys {
+            self.save_account_to_json(pubkey, path.join(format!("{pubkey}.json")))?;
+        }
+        Ok(())
+    }
+
+    /// Installs the accounts in a recorded ``getAccountInfo``, ``getMultipleAccounts``
+    /// or ``getProgramAccounts`` response and returns their addresses.
+    ///
+    /// ``addresses`` is required for ``getAccountInfo`` and ``getMultipleAccounts``,
+    /// since those responses don't include the account addresses.
+    /// Accounts missing from the response are skipped.
+    #[pyo3(signature = (resp, addresses=None))]
+    pub fn add_accounts_from_rpc_response(
+        &mut self,
+        resp: AccountsResp,
+        addresses: Option<Vec<Pubkey>>,
+    ) -> PyResult<Vec<Pubkey>> {
+        let (resp_keys, maybe_accounts): (Option<Vec<Pubkey>>, Vec<Option<Account>>) = match resp {
+            AccountsResp::AccountInfo(r) => (None, vec![r.value]),
+            AccountsResp::MultipleAccounts(r) => (None, r.value),
+            AccountsResp::ProgramAccounts(r) => split_keyed_accounts(r.0),
+            AccountsResp::ProgramAccountsWithContext(r) => split_keyed_accounts(r.value),
+        };
+        let pubkeys = match (resp_keys, addresses) {
+            (Some(keys), _) => keys,
+            (None, Some(addresses)) => {
+                if addresses.len() != maybe_accounts.len() {
+                    return Err(PyValueError::new_err(format!(
+                        "Expected {} addresses, got {}",
+                        maybe_accounts.len(),
+                        addresses.len()
+                    )));
+                }
+                addresses
+            }
+            (None, None) => {
+                return Err(PyValueError::new_err(
+                    "addresses must be provided for this response type",
+                ))
+            }
+        };
+        let mut accounts: Vec<(Pubkey, Account)> = pubkeys
+            .into_iter()
+            .zip(maybe_accounts)
+            .filter_map(|(pubkey, acc)| acc.map(|a| (pubkey, a)))
+            .collect();
+        for (pubkey, acc) in &accounts {
+            if acc.executable && acc.owner.0 == bpf_loader_upgradeable::id() {
+                if let Ok(UpgradeableLoaderState::Program {
+                    programdata_address,
+                }) = bincode::deserialize(&acc.data)
+                {
+                    let found = accounts.iter().any(|x| x.0 .0 == programdata_address)
+                        || self.svm.get_account(&programdata_address).is_some();
+                    if !found {
+                        return Err(PyValueError::new_err(format!(
+                            "ProgramData account {programdata_address} for program {pubkey} is missing from the response"
+                        )));
+                    }
+                }
+            }
+        }
+        // ProgramData accounts must exist before the programs that point to them.
+        accounts.sort_by_key(|(_, acc)| acc.executable);
+        for (pubkey, acc) in &accounts {
+            self.set_account(*pubkey, acc)?;
+        }
+        Ok(accounts.into_iter().map(|x| x.0).collect())
     }
 
     pub fn set_compute_budget(&mut self, budget: &ComputeBudget) {
-        self.0.set_compute_budget(budget.0);
+        self.svm.set_compute_budget(budget.0);
     }
 
     /// Enables or disables sigverify
     pub fn set_sigverify(&mut self, sigverify: bool) {
-        self.0.set_sigverify(sigverify);
+        self.svm.set_sigverify(sigverify);
     }
 
     pub fn set_blockhash_check(&mut self, check: bool) {
-        self.0.set_blockhash_check(check);
+        self.blockhash_check = check;
+    }
+
+    /// Changes the feature set and rebuilds the builtins, precompiles
+    /// and compute budget that depend on it, removing the builtins and precompiles
+    /// that the new feature set disables.
+    pub fn set_feature_set(&mut self, feature_set: &FeatureSet) -> PyResult<()> {
+        let features = feature_set.0.clone();
+        if let Some(budget) = self.svm.get_compute_budget() {
+            let defaults = ComputeBudgetOriginal::new_with_defaults(
+                features.is_active(&raise_cpi_nesting_limit_to_8::id()),
+            );
+            self.svm.set_compute_budget(ComputeBudgetOriginal {
+                max_instruction_stack_depth: defaults.max_instruction_stack_depth,
+                ..budget
+            });
+        }
+        self.feature_set = features.clone();
+        self.svm.set_feature_set(features);
+        self.svm.set_builtins();
+        self.svm.set_precompiles();
+        if self.tracing {
+            trace::install_loaders(&mut self.svm, true);
+        }
+        self.remove_disabled_programs()
     }
 
     pub fn set_sysvars(&mut self) {
-        self.0.set_sysvars()
+        self.svm.set_sysvars()
     }
 
     pub fn set_builtins(&mut self) {
-        self.0.set_builtins()
+        self.svm.set_builtins();
+        if self.tracing {
+            trace::install_loaders(&mut self.svm, true);
+        }
     }
 
-    pub fn set_lamports(&mut self, lamports: u64) {
-        self.0.set_lamports(lamports)
+    pub fn set_lamports(&mut self, lamports: u64) -> PyResult<()> {
+        let Some(seeded) = &self.seeded else {
+            let funded = fund_airdrop_keypair(&mut self.svm, lamports);
+            self.airdrop_pubkey = self.airdrop_pubkey.or(funded);
+            return Ok(());
+        };
+        self.svm
+            .set_account(
+                seeded.faucet.pubkey(),
+                AccountOriginal {
+                    lamports,
+                    owner: system_program::id(),
+                    ..Default::default()
+                },
+            )
+            .map_err(to_py_err)
+    }
+
+    /// Returns a new keypair, derived from the seed if the instance has one.
+    pub fn new_keypair(&mut self) -> Keypair {
+        Keypair(
+            self.seeded
+                .as_mut()
+                .map_or_else(KeypairOriginal::new, Seeded::next_keypair),
+        )
     }
 
     /// Includes the standard SPL programs
     pub fn set_default_programs(&mut self) {
-        self.0.set_default_programs();
+        self.svm.set_default_programs();
     }
 
     pub fn set_transaction_history(&mut self, capacity: usize) {
-        self.0.set_transaction_history(capacity)
+        self.svm.set_transaction_history(capacity)
     }
 
     #[pyo3(signature = (limit=None))]
     pub fn set_log_bytes_limit(&mut self, limit: Option<usize>) {
-        self.0.set_log_bytes_limit(limit);
+        self.svm.set_log_bytes_limit(limit);
     }
 
     pub fn set_precompiles(&mut self) {
-        self.0.set_precompiles();
+        self.svm.set_precompiles();
     }
 
     pub fn minimum_balance_for_rent_exemption(&self, data_len: usize) -> u64 {
-        self.0.minimum_balance_for_rent_exemption(data_len)
+        self.svm.minimum_balance_for_rent_exemption(data_len)
     }
 
     pub fn get_account(&self, pubkey: Pubkey) -> Option<Account> {
-        self.0.get_account(&pubkey.0).map(Account::from)
+        self.svm.get_account(&pubkey.0).map(Account::from)
+    }
+
+    /// Every account, sorted by address.
+    pub fn accounts(&self) -> Vec<(Pubkey, Account)> {
+        self.matching_accounts(|_| true)
+    }
+
+    /// The accounts owned by ``program_id`` that pass every filter, sorted by address.
+    #[pyo3(signature = (program_id, filters=None))]
+    pub fn get_program_accounts(
+        &self,
+        program_id: Pubkey,
+        filters: Option<Vec<RpcFilterType>>,
+    ) -> PyResult<Vec<(Pubkey, Account)>> {
+        let filters: Vec<RpcFilterTypeOriginal> = filters
+            .unwrap_or_default()
+            .into_iter()
+            .map(RpcFilterTypeOriginal::from)
+            .collect();
+        for filter in &filters {
+            filter
+                .verify()
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        }
+        Ok(self.matching_accounts(|acc| {
+            *acc.owner() == program_id.0
+                && filters.iter().all(|filter| match filter {
+                    RpcFilterTypeOriginal::DataSize(size) => acc.data().len() as u64 == *size,
+                    RpcFilterTypeOriginal::Memcmp(memcmp) => memcmp.bytes_match(acc.data()),
+                    RpcFilterTypeOriginal::TokenAccountState => {
+                        token::is_token_account_data(acc.data())
+                    }
+                })
+        }))
     }
 
     pub fn set_account(&mut self, pubkey: Pubkey, data: &Account) -> PyResult<()> {
-        self.0
+        self.svm
             .set_account(pubkey.0, AccountOriginal::from(data.clone()))
             .map_err(to_py_err)
     }
 
+    #[pyo3(signature = (authority, decimals, mint=None, freeze_authority=None, token_program_id=None))]
+    pub fn create_mint(
+        &mut self,
+        authority: Pubkey,
+        decimals: u8,
+        mint: Option<Pubkey>,
+        freeze_authority: Option<Pubkey>,
+        token_program_id: Option<Pubkey>,
+    ) -> PyResult<Pubkey> {
+        let mint = match mint {
+            Some(mint) => mint.0,
+            None => self.unique_pubkey(),
+        };
+        token::create_mint(
+            &mut self.svm,
+            mint,
+            authority.0,
+            decimals,
+            freeze_authority.map(|x| x.0),
+            token_program_id.map_or(spl_token_interface::ID, |x| x.0),
+        )?;
+        Ok(Pubkey(mint))
+    }
+
+    pub fn create_ata(&mut self, owner: Pubkey, mint: Pubkey) -> PyResult<Pubkey> {
+        token::create_ata(&mut self.svm, owner.0, mint.0).map(Pubkey)
+    }
+
+    pub fn mint_to(&mut self, mint: Pubkey, destination: Pubkey, amount: u64) -> PyResult<()> {
+        token::mint_to(&mut self.svm, mint.0, destination.0, amount)
+    }
+
+    pub fn set_token_balance(&mut self, account: Pubkey, amount: u64) -> PyResult<()> {
+        token::set_token_balance(&mut self.svm, account.0, amount)
+    }
+
     pub fn get_balance(&self, pubkey: Pubkey) -> Option<u64> {
-        self.0.get_balance(&pubkey.0)
+        self.svm.get_balance(&pubkey.0)
     }
 
     pub fn latest_blockhash(&self) -> Blockhash {
-        Blockhash(self.0.latest_blockhash())
+        Blockhash(self.latest_blockhash)
     }
 
     pub fn get_transaction(&self, signature: Signature) -> Option<TransactionResult> {
-        self.0
+        self.svm
             .get_transaction(&signature.0)
             .map(|x| x.clone().into())
     }
 
     pub fn airdrop(&mut self, pubkey: Pubkey, lamports: u64) -> TransactionResult {
-        self.0.airdrop(&pubkey.0, lamports).into()
+        let res = match &self.seeded {
+            None => self.svm.airdrop(&pubkey.0, lamports),
+            Some(seeded) => {
+                let faucet = &seeded.faucet;
+                let tx = TransactionOriginal::new_signed_with_payer(
+                    &[system_instruction::transfer(
+                        &faucet.pubkey(),
+                        &pubkey.0,
+                        lamports,
+                    )],
+                    Some(&faucet.pubkey()),
+                    &[faucet],
+                    self.latest_blockhash,
+                );
+                self.svm.send_transaction(tx)
+            }
+        };
+        self.record_history(&res, vec![pubkey.0]);
+        res.into()
+    }
+
+    /// Returns the signatures of the transactions in the transaction history
+    /// that reference ``address``, newest first, like the ``getSignaturesForAddress`` RPC method.
+    ///
+    /// ``before`` and ``until`` are exclusive bounds. ``limit`` defaults to and is capped at 1000.
+    #[pyo3(signature = (address, before=None, until=None, limit=None))]
+    pub fn get_signatures_for_address(
+        &self,
+        address: Pubkey,
+        before: Option<Signature>,
+        until: Option<Signature>,
+        limit: Option<usize>,
+    ) -> Vec<RpcConfirmedTransactionStatusWithSignature> {
+        let mut entries = self.history_entries().rev().peekable();
+        if let Some(before) = before {
+            while entries.next_if(|entry| entry.signature != before).is_some() {}
+            entries.next();
+        }
+        let limit = limit.map_or(MAX_SIGNATURES_FOR_ADDRESS, |x| {
+            x.min(MAX_SIGNATURES_FOR_ADDRESS)
+        });
+        entries
+            .take_while(|entry| Some(entry.signature) != until)
+            .filter(|entry| entry.addresses.contains(&address.0))
+            .take(limit)
+            .map(|entry| {
+                RpcConfirmedTransactionStatusWithSignature::new(
+                    entry.signature,
+                    entry.slot,
+                    self.history_err(&entry.signature),
+                    None,
+                    Some(entry.block_time),
+                    Some(TransactionConfirmationStatus::Finalized),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the status of each signature in the transaction history,
+    /// or None for signatures it doesn't contain, like the ``getSignatureStatuses`` RPC method.
+    pub fn get_signature_statuses(
+        &self,
+        signatures: Vec<Signature>,
+    ) -> Vec<Option<TransactionStatus>> {
+        signatures
+            .into_iter()
+            .map(|signature| {
+                let entry = self
+                    .history_entries()
+                    .rev()
+                    .find(|entry| entry.signature == signature)?;
+                let err = self.history_err(&signature);
+                Some(TransactionStatus::new(
+                    entry.slot,
+                    None,
+                    err.clone(),
+                    err,
+                    Some(TransactionConfirmationStatus::Finalized),
+                ))
+            })
+            .collect()
+    }
+
+    /// Registers ``callback(program_id, accounts, data)`` as the program at ``program_id``.
+    /// ``accounts`` is a list of ``AccountInfo`` views that the callback may modify.
+    /// The callback returns None on success, ``bytes`` to also set return data,
+    /// or an ``InstructionErrorType`` to fail. A raised exception fails the instruction
+    /// with ``ProgramFailedToComplete`` and is written to the logs.
+    pub fn add_mock_program(&mut self, program_id: Pubkey, callback: Py<PyAny>) -> PyResult<()> {
+        self.svm.add_builtin(program_id.0, mock::MockProgram::vm);
+        // The runtime only runs builtins directly when they are owned by the native loader.
+        let account = AccountOriginal {
+            lamports: 1,
+            data: vec![],
+            owner: native_loader::id(),
+            executable: true,
+            rent_epoch: 0,
+        };
+        self.svm
+            .set_account(program_id.0, account)
+            .map_err(to_py_err)?;
+        self.mock_programs.insert(program_id.0, callback);
+        Ok(())
     }
 
     pub fn add_program_from_file(&mut self, program_id: Pubkey, path: PathBuf) -> PyResult<()> {
         let res = self
-            .0
+            .svm
             .add_program_from_file(program_id.0, path.to_str().unwrap());
         res.map_err(|e| PyFileNotFoundError::new_err(e.to_string()))
     }
 
     /// Adds am SBF program to the test environment.
     pub fn add_program(&mut self, program_id: Pubkey, program_bytes: &[u8]) -> PyResult<()> {
-        self.0
+        self.svm
             .add_program(program_id.0, program_bytes)
             .map_err(to_py_err)
     }
 
-    pub fn send_transaction(&mut self, tx: TransactionType) -> TransactionResult {
-        let res = self.0.send_transaction(tx);
-        TransactionResult::from(res)
+    /// Deploys an SBF program through the upgradeable BPF loader, creating
+    /// the Program and ProgramData accounts that the loader would create.
+    /// If ``upgrade_authority`` is None, the program is immutable.
+    #[pyo3(signature = (program_id, program_bytes, upgrade_authority=None))]
+    pub fn add_upgradeable_program(
+        &mut self,
+        program_id: Pubkey,
+        program_bytes: &[u8],
+        upgrade_authority: Option<Pubkey>,
+    ) -> PyResult<()> {
+        let programdata_address = get_program_data_address(&program_id.0);
+        let slot = self.svm.get_sysvar::<ClockOriginal>().slot;
+        let mut programdata = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+            slot,
+            upgrade_authority_address: upgrade_authority.map(|x| x.0),
+        })
+        .map_err(to_py_err)?;
+        programdata.resize(UpgradeableLoaderState::size_of_programdata_metadata(), 0);
+        programdata.extend_from_slice(program_bytes);
+        let program = bincode::serialize(&UpgradeableLoaderState::Program {
+            programdata_address,
+        })
+        .map_err(to_py_err)?;
+        self.svm
+            .set_account(
+                programdata_address,
+                AccountOriginal {
+                    lamports: self
+                        .svm
+                        .minimum_balance_for_rent_exemption(programdata.len()),
+                    data: programdata,
+                    owner: bpf_loader_upgradeable::id(),
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .map_err(to_py_err)?;
+        self.svm
+            .set_account(
+                program_id.0,
+                AccountOriginal {
+                    lamports: self.svm.minimum_balance_for_rent_exemption(program.len()),
+                    data: program,
+                    owner: bpf_loader_upgradeable::id(),
+                    executable: true,
+                    rent_epoch: 0,
+                },
+            )
+            .map_err(to_py_err)
+    }
+
+    /// Upgrades a program deployed with ``add_upgradeable_program`` by sending
+    /// a real loader ``Upgrade`` instruction signed by ``upgrade_authority``.
+    /// If the new program is larger, the ProgramData account is first extended
+    /// in a separate transaction and the slot is advanced by one.
+    /// ``payer`` defaults to ``upgrade_authority`` and also receives the buffer's lamports.
+    ///
+    /// The transactions are processed like ``send_transaction``.
+    /// If one of them fails, the buffer account is removed.
+    #[pyo3(signature = (program_id, program_bytes, upgrade_authority, payer=None))]
+    pub fn upgrade_program(
+        slf: &Bound<'_, Self>,
+        program_id: Pubkey,
+        program_bytes: &[u8],
+        upgrade_authority: &Keypair,
+        payer: Option<&Keypair>,
+    ) -> PyResult<TransactionResult> {
+        let authority = &upgrade_authority.0;
+        let payer = payer.map_or(authority, |x| &x.0);
+        let mut this = slf.borrow_mut();
+        let programdata_len = this
+            .svm
+            .get_account(&get_program_data_address(&program_id.0))
+            .map_or(0, |x| x.data.len());
+        let required_len = UpgradeableLoaderState::size_of_programdata(program_bytes.len());
+        let additional_len = required_len.saturating_sub(programdata_len);
+        let additional_bytes = u32::try_from(additional_len).map_err(|_| {
+            PyValueError::new_err(format!(
+                "Cannot extend the program by {additional_len} bytes"
+            ))
+        })?;
+        let buffer_address = this.unique_pubkey();
+        let mut buffer = bincode::serialize(&UpgradeableLoaderState::Buffer {
+            authority_address: Some(authority.pubkey()),
+        })
+        .map_err(to_py_err)?;
+        buffer.extend_from_slice(program_bytes);
+        let buffer_lamports = this.svm.minimum_balance_for_rent_exemption(buffer.len());
+        this.svm
+            .set_account(
+                buffer_address,
+                AccountOriginal {
+                    lamports: buffer_lamports,
+                    data: buffer,
+                    owner: bpf_loader_upgradeable::id(),
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .map_err(to_py_err)?;
+        let mut ixs = Vec::with_capacity(2);
+        if additional_bytes > 0 {
+            let extend_ix = if this
+                .feature_set
+                .is_active(&enable_extend_program_checked::id())
+            {
+                loader_v3_instruction::extend_program_checked(
+                    &program_id.0,
+                    &authority.pubkey(),
+                    Some(&payer.pubkey()),
+                    additional_bytes,
+                )
+            } else {
+                loader_v3_instruction::extend_program(
+                    &program_id.0,
+                    Some(&payer.pubkey()),
+                    additional_bytes,
+                )
+            };
+            ixs.push(extend_ix);
+        }
+        ixs.push(loader_v3_instruction::upgrade(
+            &program_id.0,
+            &buffer_address,
+            &authority.pubkey(),
+            &payer.pubkey(),
+        ));
+        let mut processed = Vec::with_capacity(ixs.len());
+        for ix in ixs {
+            if !processed.is_empty() {
+                // The loader rejects upgrades in the slot where the program was extended.
+                let slot = this.svm.get_sysvar::<ClockOriginal>().slot;
+                this.warp_to_slot(slot + 1);
+            }
+            let tx = TransactionOriginal::new_signed_with_payer(
+                &[ix],
+                Some(&payer.pubkey()),
+                &[payer, authority],
+                this.latest_blockhash,
+            );
+            let (res, changed) = this.process_transaction(tx.into());
+            this.advance_slot();
+            let failed = matches!(res, TransactionResult::Err(_));
+            processed.push((res, changed));
+            if failed {
+                // A successful upgrade closes the buffer, so only a failure leaves it behind.
+                this.svm
+                    .set_account(buffer_address, AccountOriginal::default())
+                    .map_err(to_py_err)?;
+                break;
+            }
+        }
+        drop(this);
+        Self::run_callbacks(slf, &processed)?;
+        let (res, _) = processed.pop().expect("the upgrade is always attempted");
+        Ok(res)
+    }
+
+    /// Creates an address lookup table holding ``addresses`` and moves to the next slot,
+    /// so that a transaction can load every address from it.
+    ///
+    /// The table is derived from the most recent slot in ``SlotHashes``. The transactions
+    /// are processed like ``send_transaction``, so they land in the transaction history
+    /// and trigger callbacks. Raises if any of them fails.
+    #[pyo3(signature = (authority, addresses, payer=None))]
+    pub fn create_lookup_table(
+        slf: &Bound<'_, Self>,
+        authority: &Keypair,
+        addresses: Vec<Pubkey>,
+        payer: Option<&Keypair>,
+    ) -> PyResult<Pubkey> {
+        let authority = &authority.0;
+        let payer = payer.map_or(authority, |x| &x.0);
+        let mut this = slf.borrow_mut();
+        let recent_slot = this
+            .svm
+            .get_sysvar::<SlotHashes>()
+            .first()
+            .map_or(0, |(slot, _)| *slot);
+        let (create_ix, table_address) = lookup_table_instruction::create_lookup_table(
+            authority.pubkey(),
+            payer.pubkey(),
+            recent_slot,
+        );
+        let ixs = std::iter::once((create_ix, vec![payer])).chain(
+            addresses
+                .chunks(LOOKUP_TABLE_ADDRESSES_PER_EXTEND)
+                .map(|chunk| {
+                    let ix = lookup_table_instruction::extend_lookup_table(
+                        table_address,
+                        authority.pubkey(),
+                        Some(payer.pubkey()),
+                        chunk.iter().map(|x| x.0).collect(),
+                    );
+                    (ix, vec![payer, authority])
+                }),
+        );
+        let mut processed = Vec::new();
+        for (ix, signers) in ixs {
+            let tx = TransactionOriginal::new_signed_with_payer(
+                &[ix],
+                Some(&payer.pubkey()),
+                &signers,
+                this.latest_blockhash,
+            );
+            let (res, changed) = this.process_transaction(tx.into());
+            this.advance_slot();
+            let failed = matches!(res, TransactionResult::Err(_));
+            processed.push((res, changed));
+            if failed {
+                break;
+            }
+        }
+        if !matches!(processed.last(), Some((TransactionResult::Err(_), _))) {
+            // Addresses added in the current slot can't be loaded until the next one.
+            let slot = this.svm.get_sysvar::<ClockOriginal>().slot;
+            this.warp_to_slot(slot + 1);
+        }
+        drop(this);
+        Self::run_callbacks(slf, &processed)?;
+        match processed.pop() {
+            Some((TransactionResult::Err(failed), _)) => Err(to_py_err(failed.inner.err)),
+            _ => Ok(Pubkey(table_address)),
+        }
+    }
+
+    pub fn send_transaction(
+        slf: &Bound<'_, Self>,
+        tx: TransactionType,
+    ) -> PyResult<TransactionResult> {
+        let processed = {
+            let mut this = slf.borrow_mut();
+            let processed = this.process_transaction(VersionedTransactionOriginal::from(tx));
+            this.advance_slot();
+            [processed]
+        };
+        Self::run_callbacks(slf, &processed)?;
+        let [(res, _)] = processed;
+        Ok(res)
+    }
+
+    /// Processes transactions in order, as a bundle landing in a single slot.
+    ///
+    /// With ``atomic``, processing stops at the first failed transaction and every
+    /// change made by the batch, including fees, is rolled back. Callbacks only run
+    /// for batches that are kept.
+    #[pyo3(signature = (txs, atomic=false))]
+    pub fn send_transactions(
+        slf: &Bound<'_, Self>,
+        txs: Vec<TransactionType>,
+        atomic: bool,
+    ) -> PyResult<Vec<TransactionResult>> {
+        let txs = txs
+            .into_iter()
+            .map(VersionedTransactionOriginal::from)
+            .collect();
+        let (processed, kept) = slf.borrow_mut().process_batch(txs, atomic);
+        if kept {
+            Self::run_callbacks(slf, &processed)?;
+        }
+        Ok(processed.into_iter().map(|(res, _)| res).collect())
+    }
+
+    /// Runs ``send_transactions`` for each ``(instance, txs)`` job on a pool of
+    /// ``max_workers`` threads, with the GIL released, and returns the results in job order.
+    ///
+    /// Each job needs its own instance. Mock programs still take the GIL while they run,
+    /// and callbacks run on the calling thread once every job has finished.
+    #[staticmethod]
+    #[pyo3(signature = (jobs, atomic=false, max_workers=None))]
+    pub fn send_transactions_parallel(
+        py: Python<'_>,
+        jobs: Vec<(Bound<'_, Self>, Vec<TransactionType>)>,
+        atomic: bool,
+        max_workers: Option<usize>,
+    ) -> PyResult<Vec<Vec<TransactionResult>>> {
+        let max_workers = match max_workers {
+            Some(0) => return Err(PyValueError::new_err("max_workers must be positive")),
+            Some(max_workers) => max_workers,
+            None => std::thread::available_parallelism().map_or(1, usize::from),
+        };
+        let mut instances = Vec::with_capacity(jobs.len());
+        let mut batches = Vec::with_capacity(jobs.len());
+        for (svm, txs) in &jobs {
+            instances.push(
+                svm.try_borrow_mut().map_err(|_| {
+                    PyValueError::new_err("Each job needs its own LiteSVM instance")
+                })?,
+            );
+            batches.push(
+                txs.iter()
+                    .cloned()
+                    .map(VersionedTransactionOriginal::from)
+                    .collect::<Vec<_>>(),
+            );
+        }
+        let mut work: Vec<_> = instances
+            .iter_mut()
+            .map(|svm| &mut **svm)
+            .zip(batches)
+            .collect();
+        let chunk_size = work.len().div_ceil(max_workers).max(1);
+        let outcomes: Vec<_> = py.detach(|| {
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = work
+                    .chunks_mut(chunk_size)
+                    .map(|chunk| {
+                        scope.spawn(move || {
+                            chunk
+                                .iter_mut()
+                                .map(|(svm, txs)| svm.process_batch(std::mem::take(txs), atomic))
+                                .collect::<Vec<_>>()
+                        })
+                    })
+                    .collect();
+                handles
+                    .into_iter()
+                    .flat_map(|handle| {
+                        handle
+                            .join()
+                            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                    })
+                    .collect()
+            })
+        });
+        drop(work);
+        drop(instances);
+        let mut results = Vec::with_capacity(outcomes.len());
+        for ((svm, _), (processed, kept)) in jobs.iter().zip(outcomes) {
+            if kept {
+                Self::run_callbacks(svm, &processed)?;
+            }
+            results.push(processed.into_iter().map(|(res, _)| res).collect());
+        }
+        Ok(results)
     }
 
     pub fn simulate_transaction(&mut self, tx: TransactionType) -> SimulateResult {
-        let res = self.0.simulate_transaction(tx);
-        SimulateResult::from(res)
+        let tx = VersionedTransactionOriginal::from(tx);
+        let _mocks = mock::activate(&self.mock_programs);
+        let instruction_programs = InstructionPrograms::new(&tx.message);
+        let fees = FeeReport::new(&tx.message, &self.feature_set);
+        let pre_accounts = self.account_diffs.then(|| self.writable_accounts(&tx));
+        let traced = self.tracing.then(|| {
+            let addresses = self.transaction_addresses(&tx);
+            trace::load_traced_programs(&self.svm, &self.feature_set, &addresses)
+        });
+        let stand_ins = self.add_stand_ins(&tx);
+        let (res, traces) = {
+            let tracer = traced.as_ref().map(trace::activate);
+            let res = self
+                .check_blockhash(&tx)
+                .and_then(|()| self.svm.simulate_transaction(tx));
+            (res, tracer.map(TraceGuard::finish))
+        };
+        self.remove_stand_ins(stand_ins);
+        let post_accounts: HashMap<_, _> = res
+            .as_ref()
+            .map(|info| info.post_accounts.iter().cloned().collect())
+            .unwrap_or_default();
+        let fees = fees.map(|fees| {
+            let payer = post_accounts.get(&fees.payer.0).map_or_else(
+                || self.svm.get_account(&fees.payer.0),
+                |acc| Some(acc.clone().into()),
+            );
+            self.with_payer(fees, payer)
+        });
+        let res = SimulateResult::from(res)
+            .with_instruction_programs(instruction_programs)
+            .with_fee_report(fees)
+            .with_traces(traces);
+        let Some(pre_accounts) = pre_accounts else {
+            return res;
+        };
+        if matches!(res, SimulateResult::Err(_)) {
+            return res;
+        }
+        let diffs = pre_accounts
+            .iter()
+            .map(|(pubkey, pre)| {
+                let post = post_accounts
+                    .get(pubkey)
+                    .map_or_else(|| pre.clone(), |acc| AccountOriginal::from(acc.clone()));
+                AccountDiff::new(*pubkey, pre, &post)
+            })
+            .collect();
+        res.with_account_diffs(diffs)
+    }
+
+    /// After each ``send_transaction`` or ``send_transactions`` batch, move to the next slot:
+    /// advance the clock by ``slot_duration_ms``, rotate the blockhash,
+    /// and update ``SlotHashes`` and ``SlotHistory``.
+    #[pyo3(signature = (enabled, slot_duration_ms=DEFAULT_SLOT_DURATION_MS))]
+    pub fn set_auto_advance(&mut self, enabled: bool, slot_duration_ms: u64) {
+        self.auto_advance = enabled.then_some(AutoAdvance {
+            slot_duration_ms,
+            pending_ms: 0,
+        });
+    }
+
+    /// Call ``callback(result, changed_accounts)`` after each ``send_transaction``.
+    /// ``changed_accounts`` holds the new state of each account the transaction modified.
+    pub fn on_transaction(&mut self, callback: Py<PyAny>) {
+        self.callbacks.transaction.push(callback);
+    }
+
+    /// Call ``callback(pubkey, account)`` with the new state of ``pubkey``
+    /// whenever a transaction sent with ``send_transaction`` modifies it.
+    pub fn on_account_change(&mut self, pubkey: Pubkey, callback: Py<PyAny>) {
+        self.callbacks.account.push((pubkey.0, callback));
+    }
+
+    /// Record how each writable account changes in the results of
+    /// ``send_transaction`` and ``simulate_transaction``.
+    pub fn set_account_diffs(&mut self, enabled: bool) {
+        self.account_diffs = enabled;
+    }
+
+    /// Record the SBF instructions executed by BPF programs in the results of
+    /// ``send_transaction`` and ``simulate_transaction``, and count sent transactions'
+    /// instructions towards ``coverage``.
+    pub fn set_tracing(&mut self, enabled: bool) {
+        if enabled != self.tracing {
+            self.tracing = enabled;
+            trace::install_loaders(&mut self.svm, enabled);
+        }
+    }
+
+    /// How many times sent transactions executed each instruction of each traced program,
+    /// including the instructions they never executed.
+    pub fn coverage(&self) -> HashMap<Pubkey, BTreeMap<u64, u64>> {
+        self.coverage
+            .iter()
+            .map(|(program_id, hits)| (Pubkey(*program_id), hits.clone()))
+            .collect()
+    }
+
+    /// ``coverage`` as one ``<program id> <pc> <hits>`` line per instruction.
+    pub fn coverage_report(&self) -> String {
+        trace::report(&self.coverage)
+    }
+
+    pub fn reset_coverage(&mut self) {
+        self.coverage.clear();
+    }
+
+    /// Creates an independent instance with the same state and settings, except for callbacks.
+    ///
+    /// Account data is shared until either instance writes to it, so forking is cheap
+    /// even when the instance holds large programs.
+    pub fn fork(&self, py: Python<'_>) -> Self {
+        Self {
+            svm: self.svm.clone(),
+            feature_set: self.feature_set.clone(),
+            account_diffs: self.account_diffs,
+            auto_advance: self.auto_advance,
+            callbacks: Callbacks::default(),
+            history: self.history.clone(),
+            mock_programs: self
+                .mock_programs
+                .iter()
+                .map(|(program_id, callback)| (*program_id, callback.clone_ref(py)))
+                .collect(),
+            seeded: self.seeded.clone(),
+            disabled_builtins: self.disabled_builtins.clone(),
+            airdrop_pubkey: self.airdrop_pubkey,
+            latest_blockhash: self.latest_blockhash,
+            blockhash_check: self.blockhash_check,
+            tracing: self.tracing,
+            coverage: Coverage::new(),
+        }
+    }
+
+    /// Copies the current state, including accounts, sysvars, blockhash, transaction history,
+    /// coverage and the number of keypairs derived from the seed.
+    pub fn snapshot(&self) -> LiteSVMSnapshot {
+        LiteSVMSnapshot {
+            svm: self.svm.clone(),
+            feature_set: self.feature_set.clone(),
+            history: self.history.clone(),
+            seeded: self.seeded.clone(),
+            disabled_builtins: self.disabled_builtins.clone(),
+            latest_blockhash: self.latest_blockhash,
+            coverage: self.coverage.clone(),
+        }
+    }
+
+    /// Rolls back to a state previously returned by ``snapshot``.
+    /// The snapshot can be restored any number of times.
+    pub fn restore(&mut self, snapshot: &LiteSVMSnapshot) {
+        self.svm = snapshot.svm.clone();
+        self.feature_set = snapshot.feature_set.clone();
+        self.history = snapshot.history.clone();
+        self.seeded = snapshot.seeded.clone();
+        self.disabled_builtins = snapshot.disabled_builtins.clone();
+        self.latest_blockhash = snapshot.latest_blockhash;
+        self.coverage = snapshot.coverage.clone();
+        if self.tracing {
+            trace::install_loaders(&mut self.svm, true);
+        }
     }
 
     pub fn expire_blockhash(&mut self) {
-        self.0.expire_blockhash()
+        // LiteSVM's own blockhash is still expired, since its airdrops are signed with it.
+        self.svm.expire_blockhash();
+        self.set_latest_blockhash(hashv(&[self.latest_blockhash.as_ref()]));
     }
 
+    /// Moves the clock to ``slot``. Moving forward records the current slot in ``SlotHashes``
+    /// and ``slot`` in ``SlotHistory``, as if the slots in between had been skipped.
     pub fn warp_to_slot(&mut self, slot: u64) {
-        self.0.warp_to_slot(slot)
+        let parent_slot = self.svm.get_sysvar::<ClockOriginal>().slot;
+        self.svm.warp_to_slot(slot);
+        if slot > parent_slot {
+            self.record_slot_transition(parent_slot, slot);
+        }
+    }
+
+    /// Crosses each epoch boundary up to the first slot of ``epoch``, recording ``StakeHistory``
+    /// so that delegations warm up and cool down, and paying ``reward_rate`` times each
+    /// account's effective stake as inflation rewards if given.
+    #[pyo3(signature = (epoch, reward_rate=None))]
+    pub fn warp_to_epoch(&mut self, epoch: u64, reward_rate: Option<f64>) -> PyResult<()> {
+        if reward_rate.is_some_and(|rate| !(0.0..=1.0).contains(&rate)) {
+            return Err(PyValueError::new_err("reward_rate must be between 0 and 1"));
+        }
+        let mut clock = self.svm.get_sysvar::<ClockOriginal>();
+        if epoch <= clock.epoch {
+            return Err(PyValueError::new_err(format!(
+                "Cannot warp to epoch {epoch} from epoch {}",
+                clock.epoch
+            )));
+        }
+        let schedule = self.svm.get_sysvar::<EpochScheduleOriginal>();
+        let new_rate_activation_epoch = self.feature_set.new_warmup_cooldown_rate_epoch(&schedule);
+        let mut history = self.svm.get_sysvar::<StakeHistoryOriginal>();
+        let mut stake_accounts: Vec<(PubkeyOriginal, AccountOriginal, StakeStateV2)> = self
+            .svm
+            .accounts_db()
+            .inner
+            .iter()
+            .filter(|(_, acc)| *acc.owner() == stake::id())
+            .filter_map(|(pubkey, acc)| {
+                let acc = AccountOriginal::from(acc.clone());
+                let state = bincode::deserialize(&acc.data).ok()?;
+                Some((*pubkey, acc, state))
+            })
+            .collect();
+        let mut epoch_rewards = None;
+        for ending_epoch in clock.epoch..epoch {
+            let mut entry = StakeHistoryEntry::default();
+            let mut total_points = 0u128;
+            let mut total_rewards = 0u64;
+            for (_, acc, state) in stake_accounts.iter_mut() {
+                let StakeStateV2::Stake(_, stake, _) = state else {
+                    continue;
+                };
+                let status = stake.delegation.stake_activating_and_deactivating(
+                    ending_epoch,
+                    &history,
+                    new_rate_activation_epoch,
+                );
+                entry.effective = entry.effective.saturating_add(status.effective);
+                entry.activating = entry.activating.saturating_add(status.activating);
+                entry.deactivating = entry.deactivating.saturating_add(status.deactivating);
+                if let Some(rate) = reward_rate {
+                    let reward = (status.effective as f64 * rate) as u64;
+                    acc.lamports = acc.lamports.saturating_add(reward);
+                    stake.delegation.stake = stake.delegation.stake.saturating_add(reward);
+                    total_points = total_points.saturating_add(u128::from(status.effective));
+                    total_rewards = total_rewards.saturating_add(reward);
+                }
+            }
+            history.add(ending_epoch, entry);
+            if reward_rate.is_some() {
+                epoch_rewards = Some(EpochRewardsOriginal {
+                    distribution_starting_block_height: schedule
+                        .get_first_slot_in_epoch(ending_epoch + 1)
+                        + 1,
+                    num_partitions: 1,
+                    parent_blockhash: self.latest_blockhash,
+                    total_points,
+                    total_rewards,
+                    // Rewards are paid out immediately rather than over several blocks.
+                    distributed_rewards: total_rewards,
+                    active: false,
+                });
+            }
+        }
+        if reward_rate.is_some() {
+            for (pubkey, mut acc, state) in stake_accounts {
+                bincode::serialize_into(acc.data.as_mut_slice(), &state).map_err(to_py_err)?;
+                self.svm.set_account(pubkey, acc).map_err(to_py_err)?;
+            }
+        }
+        if let Some(epoch_rewards) = epoch_rewards {
+            self.svm.set_sysvar(&epoch_rewards);
+        }
+        self.svm.set_sysvar(&history);
+        let parent_slot = clock.slot;
+        clock.slot = schedule.get_first_slot_in_epoch(epoch);
+        clock.epoch = epoch;
+        clock.leader_schedule_epoch = schedule.get_leader_schedule_epoch(clock.slot);
+        clock.epoch_start_timestamp = clock.unix_timestamp;
+        self.svm.set_sysvar(&clock);
+        self.record_slot_transition(parent_slot, clock.slot);
+        Ok(())
     }
 
     pub fn get_compute_budget(&self) -> Option<ComputeBudget> {
-        self.0.get_compute_budget().map(ComputeBudget)
+        self.svm.get_compute_budget().map(ComputeBudget)
     }
 
     pub fn get_clock(&self) -> Clock {
-        Clock(self.0.get_sysvar::<ClockOriginal>())
+        Clock(self.svm.get_sysvar::<ClockOriginal>())
     }
 
     pub fn set_clock(&mut self, clock: &Clock) {
-        self.0.set_sysvar(&clock.0)
+        self.svm.set_sysvar(&clock.0)
     }
 
     pub fn get_rent(&self) -> Rent {
-        Rent(self.0.get_sysvar::<RentOriginal>())
+        Rent(self.svm.get_sysvar::<RentOriginal>())
     }
 
     pub fn set_rent(&mut self, rent: &Rent) {
-        self.0.set_sysvar(&rent.0)
+        self.svm.set_sysvar(&rent.0)
     }
 
     pub fn get_epoch_rewards(&self) -> EpochRewards {
-        EpochRewards(self.0.get_sysvar::<EpochRewardsOriginal>())
+        EpochRewards(self.svm.get_sysvar::<EpochRewardsOriginal>())
     }
 
     pub fn set_epoch_rewards(&mut self, rewards: &EpochRewards) {
-        self.0.set_sysvar(&rewards.0)
+        self.svm.set_sysvar(&rewards.0)
     }
 
     pub fn get_epoch_schedule(&self) -> EpochSchedule {
-        EpochSchedule(self.0.get_sysvar::<EpochScheduleOriginal>())
+        EpochSchedule(self.svm.get_sysvar::<EpochScheduleOriginal>())
     }
 
     pub fn set_epoch_schedule(&mut self, schedule: &EpochSchedule) {
-        self.0.set_sysvar(&schedule.0)
+        self.svm.set_sysvar(&schedule.0)
     }
 
     pub fn get_last_restart_slot(&self) -> u64 {
-        self.0.get_sysvar::<LastRestartSlot>().last_restart_slot
+        self.svm.get_sysvar::<LastRestartSlot>().last_restart_slot
     }
 
     pub fn set_last_restart_slot(&mut self, slot: u64) {
-        self.0.set_sysvar::<LastRestartSlot>(&LastRestartSlot {
+        self.svm.set_sysvar::<LastRestartSlot>(&LastRestartSlot {
             last_restart_slot: slot,
         })
     }
 
     pub fn get_slot_hashes(&self) -> Vec<(u64, Blockhash)> {
-        let fetched = self.0.get_sysvar::<SlotHashes>();
+        let fetched = self.svm.get_sysvar::<SlotHashes>();
         fetched
             .slot_hashes()
             .iter()
@@ -315,28 +2004,30 @@ impl LiteSVM {
             intermediate.push((h.0, converted_hash));
         }
         let converted = SlotHashes::from_iter(intermediate);
-        self.0.set_sysvar::<SlotHashes>(&converted);
+        self.svm.set_sysvar::<SlotHashes>(&converted);
     }
 
     pub fn get_slot_history(&self) -> SlotHistory {
-        SlotHistory(self.0.get_sysvar::<SlotHistoryOriginal>())
+        SlotHistory(self.svm.get_sysvar::<SlotHistoryOriginal>())
     }
 
     pub fn set_slot_history(&mut self, history: &SlotHistory) {
-        self.0.set_sysvar::<SlotHistoryOriginal>(&history.0)
+        self.svm.set_sysvar::<SlotHistoryOriginal>(&history.0)
     }
 
     pub fn get_stake_history(&self) -> StakeHistory {
-        StakeHistory(self.0.get_sysvar::<StakeHistoryOriginal>())
+        StakeHistory(self.svm.get_sysvar::<StakeHistoryOriginal>())
     }
 
     pub fn set_stake_history(&mut self, history: &StakeHistory) {
-        self.0.set_sysvar::<StakeHistoryOriginal>(&history.0)
+        self.svm.set_sysvar::<StakeHistoryOriginal>(&history.0)
     }
 }
 
 pub fn include_litesvm(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<FeatureSet>()?;
     m.add_class::<LiteSVM>()?;
+    m.add_class::<LiteSVMSnapshot>()?;
+    m.add_class::<mock::AccountInfo>()?;
     Ok(())
 }
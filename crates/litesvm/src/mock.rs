@@ -0,0 +1,172 @@
+//! Programs implemented by Python callables.
+//!
+//! Each mock is registered with LiteSVM as a builtin that looks up its callable
+//! in a thread-local registry. ``LiteSVM`` fills the registry for the duration of
+//! each call that executes transactions.
+use {
+    pyo3::{prelude::*, types::PyBytes},
+    solana_instruction::error::InstructionError,
+    solana_program_runtime::{
+        declare_process_instruction, invoke_context::InvokeContext, stable_log,
+    },
+    solana_pubkey::Pubkey as PubkeyOriginal,
+    solders_pubkey::Pubkey,
+    solders_transaction_error::InstructionErrorType,
+    std::{cell::RefCell, collections::HashMap},
+};
+
+/// Compute units charged for each call to a mock program.
+const MOCK_PROGRAM_COMPUTE_UNITS: u64 = 150;
+
+thread_local! {
+    static MOCK_PROGRAMS: RefCell<HashMap<PubkeyOriginal, Py<PyAny>>> = RefCell::new(HashMap::new());
+}
+
+/// An account passed to a mock program.
+///
+/// Changes to ``lamports``, ``data`` and ``owner`` are applied when the program returns
+/// successfully, subject to the usual runtime checks: for example, only the owner
+/// of a writable account can change its data.
+#[pyclass(module = "solders.litesvm", subclass)]
+#[derive(Debug, Clone)]
+pub struct AccountInfo {
+    /// Pubkey: The account address.
+    #[pyo3(get)]
+    pub key: Pubkey,
+    /// bool: Whether the account signed the transaction.
+    #[pyo3(get)]
+    pub is_signer: bool,
+    /// bool: Whether the account is writable.
+    #[pyo3(get)]
+    pub is_writable: bool,
+    /// int: The account balance.
+    #[pyo3(get, set)]
+    pub lamports: u64,
+    /// bytes: The account data.
+    #[pyo3(get, set)]
+    pub data: Vec<u8>,
+    /// Pubkey: The program that owns the account.
+    #[pyo3(get, set)]
+    pub owner: Pubkey,
+}
+
+#[pymethods]
+impl AccountInfo {
+    fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+/// What a mock program returned: an error, return data, or None for plain success.
+#[derive(FromPyObject)]
+enum MockReturn {
+    Err(InstructionErrorType),
+    ReturnData(Vec<u8>),
+}
+
+/// Makes ``mocks`` available to the transactions executed until the guard is dropped.
+pub(crate) struct MockGuard(Option<HashMap<PubkeyOriginal, Py<PyAny>>>);
+
+impl Drop for MockGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            MOCK_PROGRAMS.with(|mocks| *mocks.borrow_mut() = previous);
+        }
+    }
+}
+
+pub(crate) fn activate(mocks: &HashMap<PubkeyOriginal, Py<PyAny>>) -> MockGuard {
+    if mocks.is_empty() {
+        return MockGuard(None);
+    }
+    let active = Python::attach(|py| {
+        mocks
+            .iter()
+            .map(|(program_id, callback)| (*program_id, callback.clone_ref(py)))
+            .collect()
+    });
+    MockGuard(Some(MOCK_PROGRAMS.with(|mocks| mocks.replace(active))))
+}
+
+declare_process_instruction!(MockProgram, MOCK_PROGRAM_COMPUTE_UNITS, |invoke_context| {
+    process_instruction(invoke_context)
+});
+
+fn process_instruction(invoke_context: &mut InvokeContext) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let program_id = *instruction_context.get_program_key()?;
+    let mut infos = Vec::new();
+    for idx in 0..instruction_context.get_number_of_instruction_accounts() {
+        let account = instruction_context.try_borrow_instruction_account(idx)?;
+        infos.push(AccountInfo {
+            key: Pubkey(*account.get_key()),
+            is_signer: account.is_signer(),
+            is_writable: account.is_writable(),
+            lamports: account.get_lamports(),
+            data: account.get_data().to_vec(),
+            owner: Pubkey(*account.get_owner()),
+        });
+    }
+    let data = instruction_context.get_instruction_data();
+    let outcome = Python::attach(|py| -> PyResult<_> {
+        let Some(callback) = MOCK_PROGRAMS.with(|mocks| {
+            mocks
+                .borrow()
+                .get(&program_id)
+                .map(|callback| callback.clone_ref(py))
+        }) else {
+            return Ok(None);
+        };
+        let accounts = infos
+            .iter()
+            .map(|info| Bound::new(py, info.clone()))
+            .collect::<PyResult<Vec<_>>>()?;
+        let returned: Option<MockReturn> = callback
+            .call1(
+                py,
+                (Pubkey(program_id), accounts.clone(), PyBytes::new(py, data)),
+            )?
+            .extract(py)?;
+        let updated: Vec<AccountInfo> = accounts
+            .iter()
+            .map(|account| account.borrow().clone())
+            .collect();
+        Ok(Some((returned, updated)))
+    });
+    let log_collector = invoke_context.get_log_collector();
+    let (returned, updated) = match outcome {
+        Ok(Some(outcome)) => outcome,
+        // The mock belongs to another LiteSVM instance.
+        Ok(None) => return Err(InstructionError::UnsupportedProgramId),
+        Err(err) => {
+            stable_log::program_log(&log_collector, &err.to_string());
+            return Err(InstructionError::ProgramFailedToComplete);
+        }
+    };
+    let return_data = match returned {
+        None => None,
+        Some(MockReturn::Err(err)) => return Err(err.into()),
+        Some(MockReturn::ReturnData(return_data)) => Some(return_data),
+    };
+    for (idx, (info, new)) in (0..).zip(infos.iter().zip(updated)) {
+        let mut account = instruction_context.try_borrow_instruction_account(idx)?;
+        // Lamports go first, since a program can't debit an account it no longer owns.
+        if new.lamports != info.lamports {
+            account.set_lamports(new.lamports)?;
+        }
+        if new.data != info.data {
+            account.set_data_from_slice(&new.data)?;
+        }
+        if new.owner != info.owner {
+            account.set_owner(new.owner.0.as_ref())?;
+        }
+    }
+    if let Some(return_data) = return_data {
+        stable_log::program_return(&log_collector, &program_id, &return_data);
+        invoke_context
+            .transaction_context
+            .set_return_data(program_id, return_data)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,173 @@
+//! Direct writes of SPL Token state, for both the Token and Token-2022 programs.
+//! Token-2022 extensions are left untouched: only the base state is read and written.
+use {
+    litesvm::LiteSVM as LiteSVMOriginal,
+    pyo3::{exceptions::PyValueError, prelude::*},
+    solana_account::Account as AccountOriginal,
+    solana_program_option::COption,
+    solana_program_pack::Pack,
+    solana_pubkey::Pubkey as PubkeyOriginal,
+    solders_traits::to_py_err,
+    spl_associated_token_account_interface::address::get_associated_token_address_with_program_id,
+    spl_token_interface::state::{Account as TokenAccount, AccountState, Mint},
+};
+
+pub(crate) const TOKEN_2022_PROGRAM_ID: PubkeyOriginal =
+    solana_pubkey::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+// Token-2022 stores the account type right after the base account.
+const TOKEN_2022_ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+const ACCOUNT_STATE_OFFSET: usize = 108;
+
+fn is_token_program(program_id: &PubkeyOriginal) -> bool {
+    *program_id == spl_token_interface::ID || *program_id == TOKEN_2022_PROGRAM_ID
+}
+
+/// Whether ``data`` holds an initialized token account, as checked by the
+/// ``TokenAccountState`` RPC filter. Works for both token programs.
+pub(crate) fn is_token_account_data(data: &[u8]) -> bool {
+    let is_account_len = data.len() == TokenAccount::LEN
+        || data.get(TokenAccount::LEN) == Some(&TOKEN_2022_ACCOUNT_TYPE_ACCOUNT);
+    is_account_len && data[ACCOUNT_STATE_OFFSET] != AccountState::Uninitialized as u8
+}
+
+/// The token amount held by ``account``, if it is an SPL Token account.
+pub(crate) fn token_amount(account: &AccountOriginal) -> Option<u64> {
+    if !is_token_program(&account.owner) || !is_token_account_data(&account.data) {
+        return None;
+    }
+    TokenAccount::unpack_from_slice(&account.data[..TokenAccount::LEN])
+        .ok()
+        .map(|x| x.amount)
+}
+
+fn get_token_state<T: Pack>(
+    svm: &LiteSVMOriginal,
+    address: &PubkeyOriginal,
+    kind: &str,
+) -> PyResult<(AccountOriginal, T)> {
+    let account = svm
+        .get_account(address)
+        .ok_or_else(|| PyValueError::new_err(format!("{kind} {address} does not exist")))?;
+    if !is_token_program(&account.owner) {
+        return Err(PyValueError::new_err(format!(
+            "{kind} {address} is owned by {}, not a token program",
+            account.owner
+        )));
+    }
+    let state = account
+        .data
+        .get(..T::LEN)
+        .and_then(|data| T::unpack_from_slice(data).ok())
+        .ok_or_else(|| PyValueError::new_err(format!("{address} is not a valid {kind}")))?;
+    Ok((account, state))
+}
+
+fn write_token_state<T: Pack>(
+    svm: &mut LiteSVMOriginal,
+    address: PubkeyOriginal,
+    mut account: AccountOriginal,
+    state: T,
+) -> PyResult<()> {
+    state.pack_into_slice(&mut account.data[..T::LEN]);
+    svm.set_account(address, account).map_err(to_py_err)
+}
+
+pub(crate) fn create_mint(
+    svm: &mut LiteSVMOriginal,
+    mint: PubkeyOriginal,
+    authority: PubkeyOriginal,
+    decimals: u8,
+    freeze_authority: Option<PubkeyOriginal>,
+    token_program_id: PubkeyOriginal,
+) -> PyResult<()> {
+    if !is_token_program(&token_program_id) {
+        return Err(PyValueError::new_err(format!(
+            "{token_program_id} is not a token program"
+        )));
+    }
+    let account = AccountOriginal {
+        lamports: svm.minimum_balance_for_rent_exemption(Mint::LEN),
+        data: vec![0; Mint::LEN],
+        owner: token_program_id,
+        executable: false,
+        rent_epoch: 0,
+    };
+    let state = Mint {
+        mint_authority: COption::Some(authority),
+        supply: 0,
+        decimals,
+        is_initialized: true,
+        freeze_authority: freeze_authority.into(),
+    };
+    write_token_state(svm, mint, account, state)
+}
+
+pub(crate) fn create_ata(
+    svm: &mut LiteSVMOriginal,
+    owner: PubkeyOriginal,
+    mint: PubkeyOriginal,
+) -> PyResult<PubkeyOriginal> {
+    let (mint_account, _) = get_token_state::<Mint>(svm, &mint, "Mint")?;
+    let token_program_id = mint_account.owner;
+    let ata = get_associated_token_address_with_program_id(&owner, &mint, &token_program_id);
+    if let Ok((_, existing)) = get_token_state::<TokenAccount>(svm, &ata, "Token account") {
+        if existing.mint == mint && existing.owner == owner {
+            return Ok(ata);
+        }
+    }
+    let account = AccountOriginal {
+        lamports: svm.minimum_balance_for_rent_exemption(TokenAccount::LEN),
+        data: vec![0; TokenAccount::LEN],
+        owner: token_program_id,
+        executable: false,
+        rent_epoch: 0,
+    };
+    let state = TokenAccount {
+        mint,
+        owner,
+        amount: 0,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    };
+    write_token_state(svm, ata, account, state)?;
+    Ok(ata)
+}
+
+pub(crate) fn set_token_balance(
+    svm: &mut LiteSVMOriginal,
+    address: PubkeyOriginal,
+    amount: u64,
+) -> PyResult<()> {
+    let (account, mut state) = get_token_state::<TokenAccount>(svm, &address, "Token account")?;
+    let (mint_account, mut mint) = get_token_state::<Mint>(svm, &state.mint, "Mint")?;
+    let supply = i128::from(mint.supply) + i128::from(amount) - i128::from(state.amount);
+    mint.supply = u64::try_from(supply)
+        .map_err(|_| PyValueError::new_err("Mint supply would be out of range"))?;
+    state.amount = amount;
+    let mint_address = state.mint;
+    write_token_state(svm, address, account, state)?;
+    write_token_state(svm, mint_address, mint_account, mint)
+}
+
+pub(crate) fn mint_to(
+    svm: &mut LiteSVMOriginal,
+    mint: PubkeyOriginal,
+    destination: PubkeyOriginal,
+    amount: u64,
+) -> PyResult<()> {
+    let (_, state) = get_token_state::<TokenAccount>(svm, &destination, "Token account")?;
+    if state.mint != mint {
+        return Err(PyValueError::new_err(format!(
+            "Token account {destination} belongs to mint {}, not {mint}",
+            state.mint
+        )));
+    }
+    let balance = state
+        .amount
+        .checked_add(amount)
+        .ok_or_else(|| PyValueError::new_err("Token amount overflow"))?;
+    set_token_balance(svm, destination, balance)
+}
@@ -0,0 +1,277 @@
+//! SBF register traces and per-instruction coverage.
+//!
+//! While tracing is enabled, ``LiteSVM`` registers ``TracingLoader`` in place of each
+//! BPF loader. Before a transaction runs, the programs it references are loaded again
+//! with instruction tracing enabled and put in a thread-local registry. The wrapper swaps
+//! the traced copy into the transaction's program cache, runs the real loader and keeps
+//! the trace the VM recorded.
+use {
+    crate::transaction_metadata::ProgramTrace,
+    agave_feature_set::FeatureSet,
+    agave_syscalls::create_program_runtime_environment_v1,
+    litesvm::LiteSVM as LiteSVMOriginal,
+    solana_account::Account as AccountOriginal,
+    solana_bpf_loader_program::Entrypoint,
+    solana_compute_budget::compute_budget::ComputeBudget as ComputeBudgetOriginal,
+    solana_loader_v3_interface::state::UpgradeableLoaderState,
+    solana_program_runtime::{
+        invoke_context::InvokeContext,
+        loaded_programs::{LoadProgramMetrics, ProgramCacheEntry, ProgramCacheEntryType},
+        solana_sbpf::{declare_builtin_function, ebpf, memory_region::MemoryMapping},
+    },
+    solana_pubkey::Pubkey as PubkeyOriginal,
+    solana_sdk_ids::{bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable},
+    solders_pubkey::Pubkey,
+    std::{
+        cell::RefCell,
+        collections::{BTreeMap, HashMap},
+        fmt::Write,
+        sync::Arc,
+    },
+};
+
+/// The loaders whose programs can be traced.
+const TRACED_LOADERS: [PubkeyOriginal; 3] = [
+    bpf_loader::ID,
+    bpf_loader_deprecated::ID,
+    bpf_loader_upgradeable::ID,
+];
+
+/// Hit counts per program counter, for each traced program.
+pub(crate) type Coverage = HashMap<PubkeyOriginal, BTreeMap<u64, u64>>;
+
+type TracedPrograms = HashMap<PubkeyOriginal, Arc<ProgramCacheEntry>>;
+
+thread_local! {
+    static TRACED_PROGRAMS: RefCell<TracedPrograms> = RefCell::new(HashMap::new());
+    static TRACES: RefCell<Vec<ProgramTrace>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Makes the traced ``programs`` available to the transactions executed until the guard
+/// is dropped, and collects their traces.
+pub(crate) struct TraceGuard {
+    previous: Option<(TracedPrograms, Vec<ProgramTrace>)>,
+}
+
+impl TraceGuard {
+    /// The traces recorded since the guard was created, in invocation order.
+    pub(crate) fn finish(self) -> Vec<ProgramTrace> {
+        TRACES.with(|traces| traces.take())
+    }
+}
+
+impl Drop for TraceGuard {
+    fn drop(&mut self) {
+        if let Some((programs, traces)) = self.previous.take() {
+            TRACED_PROGRAMS.with(|active| *active.borrow_mut() = programs);
+            TRACES.with(|active| *active.borrow_mut() = traces);
+        }
+    }
+}
+
+pub(crate) fn activate(programs: &TracedPrograms) -> TraceGuard {
+    let previous = (
+        TRACED_PROGRAMS.with(|active| active.replace(programs.clone())),
+        TRACES.with(|active| active.take()),
+    );
+    TraceGuard {
+        previous: Some(previous),
+    }
+}
+
+declare_builtin_function!(
+    /// Runs the BPF loader, tracing the programs in the thread-local registry.
+    TracingLoader,
+    fn rust(
+        invoke_context: &mut InvokeContext,
+        arg0: u64,
+        arg1: u64,
+        arg2: u64,
+        arg3: u64,
+        arg4: u64,
+        memory_mapping: &mut MemoryMapping,
+    ) -> Result<u64, Box<dyn std::error::Error>> {
+        let program_id = *invoke_context
+            .transaction_context
+            .get_current_instruction_context()?
+            .get_program_key()?;
+        let traced = TRACED_PROGRAMS.with(|programs| programs.borrow().get(&program_id).cloned());
+        let Some(entry) = traced else {
+            return Entrypoint::rust(invoke_context, arg0, arg1, arg2, arg3, arg4, memory_mapping);
+        };
+        invoke_context
+            .program_cache_for_tx_batch
+            .replenish(program_id, entry);
+        // Reserve the slot now so that traces stay in invocation order across CPIs.
+        let index = TRACES.with(|traces| {
+            let mut traces = traces.borrow_mut();
+            traces.push(ProgramTrace {
+                program_id: Pubkey(program_id),
+                stack_height: invoke_context.get_stack_height(),
+                trace: Vec::new(),
+            });
+            traces.len() - 1
+        });
+        let result = Entrypoint::rust(invoke_context, arg0, arg1, arg2, arg3, arg4, memory_mapping);
+        if let Ok(syscall_context) = invoke_context.get_syscall_context_mut() {
+            let trace = std::mem::take(&mut syscall_context.trace_log);
+            TRACES.with(|traces| {
+                if let Some(program_trace) = traces.borrow_mut().get_mut(index) {
+                    program_trace.trace = trace;
+                }
+            });
+        }
+        result
+    }
+);
+
+/// Registers ``TracingLoader`` as each BPF loader if ``enabled``, or the real loader otherwise.
+pub(crate) fn install_loaders(svm: &mut LiteSVMOriginal, enabled: bool) {
+    for loader in TRACED_LOADERS {
+        // ``add_builtin`` replaces the loader's account with one owned by the BPF loader.
+        let Some(account) = svm.get_account(&loader) else {
+            continue;
+        };
+        if enabled {
+            svm.add_builtin(loader, TracingLoader::vm);
+        } else {
+            svm.add_builtin(loader, Entrypoint::vm);
+        }
+        let _ = svm.set_account(loader, account);
+    }
+}
+
+/// Loads the BPF programs among ``addresses`` with instruction tracing enabled.
+/// Programs that fail to load are left out and run untraced.
+pub(crate) fn load_traced_programs(
+    svm: &LiteSVMOriginal,
+    feature_set: &FeatureSet,
+    addresses: &[PubkeyOriginal],
+) -> TracedPrograms {
+    let mut programs = HashMap::new();
+    let budget = svm
+        .get_compute_budget()
+        .unwrap_or_else(|| ComputeBudgetOriginal::new_with_defaults(false));
+    let Ok(environment) = create_program_runtime_environment_v1(
+        &feature_set.runtime_features(),
+        &budget.to_budget(),
+        false,
+        true,
+    ) else {
+        return programs;
+    };
+    let environment = Arc::new(environment);
+    let slot = svm.get_sysvar::<solana_clock::Clock>().slot;
+    for address in addresses {
+        if programs.contains_key(address) {
+            continue;
+        }
+        let Some((loader, elf)) = program_elf(svm, address) else {
+            continue;
+        };
+        if let Ok(entry) = ProgramCacheEntry::new(
+            &loader,
+            environment.clone(),
+            slot,
+            slot,
+            &elf,
+            elf.len(),
+            &mut LoadProgramMetrics::default(),
+        ) {
+            programs.insert(*address, Arc::new(entry));
+        }
+    }
+    programs
+}
+
+/// The loader and ELF of the program at ``address``, if it is a BPF program.
+fn program_elf(
+    svm: &LiteSVMOriginal,
+    address: &PubkeyOriginal,
+) -> Option<(PubkeyOriginal, Vec<u8>)> {
+    let AccountOriginal {
+        owner,
+        data,
+        executable,
+        ..
+    } = svm.get_account(address)?;
+    if !executable {
+        return None;
+    }
+    if owner == bpf_loader::ID || owner == bpf_loader_deprecated::ID {
+        return Some((owner, data));
+    }
+    if owner != bpf_loader_upgradeable::ID {
+        return None;
+    }
+    let UpgradeableLoaderState::Program {
+        programdata_address,
+    } = bincode::deserialize(&data).ok()?
+    else {
+        return None;
+    };
+    let programdata = svm.get_account(&programdata_address)?;
+    let elf = programdata
+        .data
+        .get(UpgradeableLoaderState::size_of_programdata_metadata()..)?;
+    Some((owner, elf.to_vec()))
+}
+
+/// The program counter of each instruction in the program's text section.
+/// The second half of an ``lddw`` instruction is skipped, since it is never executed.
+fn instruction_pcs(entry: &ProgramCacheEntry) -> Vec<u64> {
+    let ProgramCacheEntryType::Loaded(executable) = &entry.program else {
+        return Vec::new();
+    };
+    let (_, text) = executable.get_text_bytes();
+    let has_lddw = !executable.get_sbpf_version().disable_lddw();
+    let mut pcs = Vec::with_capacity(text.len() / ebpf::INSN_SIZE);
+    let mut pc = 0;
+    while let Some(opcode) = text.get(pc * ebpf::INSN_SIZE) {
+        pcs.push(pc as u64);
+        pc += if has_lddw && *opcode == ebpf::LD_DW_IMM {
+            2
+        } else {
+            1
+        };
+    }
+    pcs
+}
+
+/// Adds the hits in ``traces`` to ``coverage``. Each traced program gets an entry for
+/// every instruction, so that instructions that never ran are counted as zero.
+pub(crate) fn record_coverage(
+    coverage: &mut Coverage,
+    programs: &TracedPrograms,
+    traces: &[ProgramTrace],
+) {
+    for (program_id, entry) in programs {
+        if !traces.iter().any(|trace| trace.program_id.0 == *program_id) {
+            continue;
+        }
+        let hits = coverage.entry(*program_id).or_default();
+        for pc in instruction_pcs(entry) {
+            hits.entry(pc).or_insert(0);
+        }
+    }
+    for trace in traces {
+        let hits = coverage.entry(trace.program_id.0).or_default();
+        for state in &trace.trace {
+            *hits.entry(state[11]).or_insert(0) += 1;
+        }
+    }
+}
+
+/// Formats ``coverage`` as one ``<program id> <pc> <hits>`` line per instruction,
+/// sorted by program and program counter.
+pub(crate) fn report(coverage: &Coverage) -> String {
+    let mut programs: Vec<_> = coverage.iter().collect();
+    programs.sort_by_key(|(program_id, _)| **program_id);
+    let mut out = String::new();
+    for (program_id, hits) in programs {
+        for (pc, count) in hits {
+            let _ = writeln!(out, "{program_id} {pc} {count}");
+        }
+    }
+    out
+}
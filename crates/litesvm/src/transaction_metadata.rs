@@ -1,4 +1,6 @@
 use {
+    agave_feature_set::FeatureSet as FeatureSetOriginal,
+    agave_precompiles::is_precompile,
     litesvm::types::{
         FailedTransactionMetadata as FailedTransactionMetadataOriginal,
         SimulatedTransactionInfo as SimulatedTransactionInfoOriginal,
@@ -8,9 +10,19 @@ use {
     pyo3::prelude::*,
     serde::{Deserialize, Serialize},
     solana_account::Account as AccountOriginal,
-    solana_message::inner_instruction::InnerInstruction as InnerInstructionOriginal,
+    solana_compute_budget_instruction::instructions_processor::process_compute_budget_instructions,
+    solana_fee::{calculate_signature_fee, FeeFeatures, SignatureCounts},
+    solana_fee_structure::FeeStructure,
+    solana_message::{
+        inner_instruction::{InnerInstruction as InnerInstructionOriginal, InnerInstructionsList},
+        VersionedMessage,
+    },
+    solana_pubkey::Pubkey as PubkeyOriginal,
+    solana_sdk_ids::{ed25519_program, secp256k1_program, secp256r1_program},
+    solana_svm_transaction::instruction::SVMInstruction,
     solders_account::Account,
     solders_instruction::CompiledInstruction,
+    solders_program_logs::{ProgramInvocation, ProgramLogs},
     solders_pubkey::Pubkey,
     solders_signature::Signature,
     solders_traits_core::transaction_status_boilerplate,
@@ -18,6 +30,8 @@ use {
     solders_transaction_return_data::TransactionReturnData,
 };
 
+use crate::token::token_amount;
+
 /// A compiled instruction that was invoked during a
 /// transaction instruction.
 #[pyclass(module = "solders.transaction_metadata", subclass)]
@@ -45,10 +59,438 @@ impl InnerInstruction {
     }
 }
 
+/// The compute units consumed by one program invocation,
+/// along with the invocations it made via CPI.
+#[pyclass(module = "solders.transaction_metadata", subclass)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct InstructionComputeUnits {
+    /// Pubkey: The invoked program.
+    #[pyo3(get)]
+    pub program_id: Pubkey,
+    /// int: Invocation stack height. Top-level instructions have height 1.
+    #[pyo3(get)]
+    pub stack_height: u8,
+    /// Optional[int]: Compute units consumed, including CPIs. None for builtin programs,
+    /// which don't log their consumption, or if the logs were truncated.
+    #[pyo3(get)]
+    pub consumed: Option<u64>,
+    /// Optional[int]: Compute units that were available when the invocation started.
+    #[pyo3(get)]
+    pub budget: Option<u64>,
+    /// Optional[CompiledInstruction]: For CPIs, the matching entry in the inner instruction trace.
+    #[pyo3(get)]
+    pub instruction: Option<CompiledInstruction>,
+    /// list[InstructionComputeUnits]: The invocations made via CPI.
+    #[pyo3(get)]
+    pub children: Vec<InstructionComputeUnits>,
+}
+
+transaction_status_boilerplate!(InstructionComputeUnits);
+
+#[solders_macros::richcmp_eq_only]
+#[solders_macros::common_methods]
+#[pymethods]
+impl InstructionComputeUnits {
+    ///
+    /// Returns:
+    ///     Optional[int]: Compute units consumed by this program itself, excluding its CPIs.
+    ///     CPIs that don't log their consumption, such as builtins, count as zero.
+    pub fn exclusive_consumed(&self) -> Option<u64> {
+        let children = self
+            .children
+            .iter()
+            .filter_map(|child| child.consumed)
+            .fold(0u64, u64::saturating_add);
+        Some(self.consumed?.saturating_sub(children))
+    }
+}
+
+/// Each top-level instruction's program and the message's static account keys,
+/// used to match logged invocations to instructions.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct InstructionPrograms {
+    programs: Vec<PubkeyOriginal>,
+    static_keys: Vec<PubkeyOriginal>,
+}
+
+impl InstructionPrograms {
+    pub(crate) fn new(msg: &VersionedMessage) -> Self {
+        let static_keys = msg.static_account_keys().to_vec();
+        let programs = msg
+            .instructions()
+            .iter()
+            .map(|ix| {
+                static_keys
+                    .get(usize::from(ix.program_id_index))
+                    .copied()
+                    .unwrap_or_default()
+            })
+            .collect();
+        Self {
+            programs,
+            static_keys,
+        }
+    }
+}
+
+fn profile_invocation(
+    invocation: &ProgramInvocation,
+    inner_instructions: Option<&[InnerInstructionOriginal]>,
+    static_keys: &[PubkeyOriginal],
+    cpi_index: &mut usize,
+) -> InstructionComputeUnits {
+    // The inner instruction trace lists CPIs in invocation order, but truncated logs
+    // can leave out invocations, so skip to the next CPI of the same program and height.
+    // Programs loaded from lookup tables can't be checked, so they match any CPI.
+    let instruction = if invocation.depth > 1 {
+        let remaining = inner_instructions
+            .and_then(|ixs| ixs.get(*cpi_index..))
+            .unwrap_or_default();
+        remaining
+            .iter()
+            .position(|inner| {
+                inner.stack_height == invocation.depth
+                    && static_keys
+                        .get(usize::from(inner.instruction.program_id_index))
+                        .is_none_or(|program_id| *program_id == invocation.program_id.0)
+            })
+            .map(|offset| {
+                *cpi_index += offset + 1;
+                CompiledInstruction(remaining[offset].instruction.clone())
+            })
+    } else {
+        None
+    };
+    let children = invocation
+        .children
+        .iter()
+        .map(|child| profile_invocation(child, inner_instructions, static_keys, cpi_index))
+        .collect();
+    InstructionComputeUnits {
+        program_id: invocation.program_id,
+        stack_height: invocation.depth,
+        consumed: invocation.consumed,
+        budget: invocation.budget,
+        instruction,
+        children,
+    }
+}
+
+pub(crate) fn compute_units_profile(
+    logs: &[String],
+    inner_instructions: &InnerInstructionsList,
+    instruction_programs: Option<&InstructionPrograms>,
+) -> Vec<InstructionComputeUnits> {
+    let static_keys = instruction_programs.map_or(&[][..], |x| x.static_keys.as_slice());
+    let mut next_idx = 0;
+    ProgramLogs::parse(logs)
+        .invocations
+        .iter()
+        .map(|invocation| {
+            // Precompiles don't log their invocations, and truncated logs can leave out
+            // instructions, so match each invocation to the next instruction of its program.
+            let idx = match instruction_programs {
+                Some(x) => x.programs.get(next_idx..).and_then(|programs| {
+                    programs
+                        .iter()
+                        .position(|program_id| {
+                            *program_id == invocation.program_id.0
+                                && !is_precompile(program_id, |_| true)
+                        })
+                        .map(|offset| next_idx + offset)
+                }),
+                None => Some(next_idx),
+            };
+            if let Some(idx) = idx {
+                next_idx = idx + 1;
+            }
+            let inner = idx
+                .and_then(|idx| inner_instructions.get(idx))
+                .map(Vec::as_slice);
+            profile_invocation(invocation, inner, static_keys, &mut 0)
+        })
+        .collect()
+}
+
+/// How a writable account changed during a transaction.
+#[pyclass(module = "solders.transaction_metadata", subclass)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AccountDiff {
+    /// Pubkey: The account address.
+    #[pyo3(get)]
+    pub pubkey: Pubkey,
+    /// int: Lamports before the transaction.
+    #[pyo3(get)]
+    pub pre_lamports: u64,
+    /// int: Lamports after the transaction.
+    #[pyo3(get)]
+    pub post_lamports: u64,
+    /// Pubkey: Owner before the transaction.
+    #[pyo3(get)]
+    pub pre_owner: Pubkey,
+    /// Pubkey: Owner after the transaction.
+    #[pyo3(get)]
+    pub post_owner: Pubkey,
+    /// int: Data length before the transaction.
+    #[pyo3(get)]
+    pub pre_data_len: usize,
+    /// int: Data length after the transaction.
+    #[pyo3(get)]
+    pub post_data_len: usize,
+    /// list[tuple[int, int]]: Half-open ``(start, end)`` byte ranges of the data that changed.
+    #[pyo3(get)]
+    pub changed_data: Vec<(usize, usize)>,
+    /// Optional[int]: The SPL Token amount before the transaction, if this was a token account.
+    #[pyo3(get)]
+    pub pre_token_amount: Option<u64>,
+    /// Optional[int]: The SPL Token amount after the transaction, if this is a token account.
+    #[pyo3(get)]
+    pub post_token_amount: Option<u64>,
+}
+
+transaction_status_boilerplate!(AccountDiff);
+
+#[solders_macros::richcmp_eq_only]
+#[solders_macros::common_methods]
+#[pymethods]
+impl AccountDiff {
+    ///
+    /// Returns:
+    ///     int: The change in lamports.
+    pub fn lamports_delta(&self) -> i128 {
+        i128::from(self.post_lamports) - i128::from(self.pre_lamports)
+    }
+
+    ///
+    /// Returns:
+    ///     Optional[int]: The change in SPL Token amount, if this is a token account.
+    ///     A token account that was created or closed counts as holding zero tokens.
+    pub fn token_amount_delta(&self) -> Option<i128> {
+        if self.pre_token_amount.is_none() && self.post_token_amount.is_none() {
+            return None;
+        }
+        let amount = |x: Option<u64>| i128::from(x.unwrap_or_default());
+        Some(amount(self.post_token_amount) - amount(self.pre_token_amount))
+    }
+
+    ///
+    /// Returns:
+    ///     bool: Whether anything about the account changed.
+    pub fn is_changed(&self) -> bool {
+        self.pre_lamports != self.post_lamports
+            || self.pre_owner != self.post_owner
+            || !self.changed_data.is_empty()
+    }
+}
+
+fn changed_ranges(pre: &[u8], post: &[u8]) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    let common = pre.len().min(post.len());
+    let mut push = |idx: usize| match ranges.last_mut() {
+        Some((_, end)) if *end == idx => *end += 1,
+        _ => ranges.push((idx, idx + 1)),
+    };
+    for idx in (0..common).filter(|&idx| pre[idx] != post[idx]) {
+        push(idx);
+    }
+    let longest = pre.len().max(post.len());
+    if longest > common {
+        match ranges.last_mut() {
+            Some((_, end)) if *end == common => *end = longest,
+            _ => ranges.push((common, longest)),
+        }
+    }
+    ranges
+}
+
+impl AccountDiff {
+    pub(crate) fn new(
+        pubkey: PubkeyOriginal,
+        pre: &AccountOriginal,
+        post: &AccountOriginal,
+    ) -> Self {
+        Self {
+            pubkey: Pubkey(pubkey),
+            pre_lamports: pre.lamports,
+            post_lamports: post.lamports,
+            pre_owner: Pubkey(pre.owner),
+            post_owner: Pubkey(post.owner),
+            pre_data_len: pre.data.len(),
+            post_data_len: post.data.len(),
+            changed_data: changed_ranges(&pre.data, &post.data),
+            pre_token_amount: token_amount(pre),
+            post_token_amount: token_amount(post),
+        }
+    }
+}
+
+/// The SBF instructions one program invocation executed.
+#[pyclass(module = "solders.transaction_metadata", subclass)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProgramTrace {
+    /// Pubkey: The invoked program.
+    #[pyo3(get)]
+    pub program_id: Pubkey,
+    /// int: Invocation stack height of the invocation. Starts at 1.
+    #[pyo3(get)]
+    pub stack_height: usize,
+    pub(crate) trace: Vec<[u64; 12]>,
+}
+
+transaction_status_boilerplate!(ProgramTrace);
+
+#[solders_macros::richcmp_eq_only]
+#[solders_macros::common_methods]
+#[pymethods]
+impl ProgramTrace {
+    ///
+    /// Returns:
+    ///     list[int]: The program counter of each executed instruction, in execution order.
+    ///     The program counter is the index of the instruction in the text section.
+    pub fn pcs(&self) -> Vec<u64> {
+        self.trace.iter().map(|state| state[11]).collect()
+    }
+
+    ///
+    /// Returns:
+    ///     list[list[int]]: Registers ``r0`` to ``r10`` before each executed instruction.
+    pub fn registers(&self) -> Vec<[u64; 11]> {
+        self.trace
+            .iter()
+            .map(|state| {
+                let mut registers = [0; 11];
+                registers.copy_from_slice(&state[..11]);
+                registers
+            })
+            .collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.trace.len()
+    }
+}
+
+/// The fees a transaction is charged and what they leave its fee payer with.
+#[pyclass(module = "solders.transaction_metadata", subclass)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FeeReport {
+    /// Pubkey: The fee payer.
+    #[pyo3(get)]
+    pub payer: Pubkey,
+    /// int: The fee per signature.
+    #[pyo3(get)]
+    pub lamports_per_signature: u64,
+    /// int: The fee for the transaction's own signatures.
+    #[pyo3(get)]
+    pub signature_fee: u64,
+    /// int: The fee for the signatures verified by the Ed25519, Secp256k1 and Secp256r1 precompiles.
+    #[pyo3(get)]
+    pub precompile_signature_fee: u64,
+    /// int: The compute unit limit, as set by ``set_compute_unit_limit`` or the default.
+    #[pyo3(get)]
+    pub compute_unit_limit: u32,
+    /// int: The compute unit price in micro-lamports, as set by ``set_compute_unit_price``.
+    #[pyo3(get)]
+    pub compute_unit_price: u64,
+    /// int: The priority fee, which is the compute unit price times the compute unit limit.
+    /// A cluster charges it, but LiteSVM does not deduct it from the fee payer.
+    #[pyo3(get)]
+    pub priority_fee: u64,
+    /// int: The fee payer's balance after the transaction.
+    #[pyo3(get)]
+    pub payer_balance: u64,
+    /// int: The rent-exempt minimum for the fee payer's account.
+    #[pyo3(get)]
+    pub payer_rent_exempt_minimum: u64,
+}
+
+transaction_status_boilerplate!(FeeReport);
+
+#[solders_macros::richcmp_eq_only]
+#[solders_macros::common_methods]
+#[pymethods]
+impl FeeReport {
+    ///
+    /// Returns:
+    ///     int: The total fee a cluster would charge, including the priority fee.
+    pub fn total(&self) -> u64 {
+        self.signature_fee
+            .saturating_add(self.precompile_signature_fee)
+            .saturating_add(self.priority_fee)
+    }
+
+    ///
+    /// Returns:
+    ///     bool: Whether the fee payer is left with a nonzero balance below the rent-exempt minimum.
+    pub fn payer_below_rent_exempt_minimum(&self) -> bool {
+        self.payer_balance != 0 && self.payer_balance < self.payer_rent_exempt_minimum
+    }
+}
+
+impl FeeReport {
+    /// The fees of ``msg``, or None if its compute budget instructions are invalid.
+    /// The payer fields are filled in by ``with_payer``.
+    pub(crate) fn new(msg: &VersionedMessage, feature_set: &FeatureSetOriginal) -> Option<Self> {
+        let keys = msg.static_account_keys();
+        let instructions = msg.instructions().iter().filter_map(|ix| {
+            keys.get(usize::from(ix.program_id_index))
+                .map(|program_id| (program_id, SVMInstruction::from(ix)))
+        });
+        let limits = process_compute_budget_instructions(instructions.clone(), feature_set).ok()?;
+        let precompile_signatures = |precompile: &PubkeyOriginal| {
+            instructions
+                .clone()
+                .filter(|(program_id, _)| *program_id == precompile)
+                .map(|(_, ix)| u64::from(ix.data.first().copied().unwrap_or_default()))
+                .sum()
+        };
+        let lamports_per_signature = FeeStructure::default().lamports_per_signature;
+        let precompile_signature_fee = calculate_signature_fee(
+            SignatureCounts {
+                num_transaction_signatures: 0,
+                num_ed25519_signatures: precompile_signatures(&ed25519_program::ID),
+                num_secp256k1_signatures: precompile_signatures(&secp256k1_program::ID),
+                num_secp256r1_signatures: precompile_signatures(&secp256r1_program::ID),
+            },
+            lamports_per_signature,
+            FeeFeatures::from(feature_set).enable_secp256r1_precompile,
+        );
+        Some(Self {
+            payer: Pubkey(keys.first().copied().unwrap_or_default()),
+            lamports_per_signature,
+            signature_fee: u64::from(msg.header().num_required_signatures)
+                .saturating_mul(lamports_per_signature),
+            precompile_signature_fee,
+            compute_unit_limit: limits.compute_unit_limit,
+            compute_unit_price: limits.compute_unit_price,
+            priority_fee: limits.get_prioritization_fee(),
+            payer_balance: 0,
+            payer_rent_exempt_minimum: 0,
+        })
+    }
+
+    pub(crate) fn with_payer(mut self, balance: u64, rent_exempt_minimum: u64) -> Self {
+        self.payer_balance = balance;
+        self.payer_rent_exempt_minimum = rent_exempt_minimum;
+        self
+    }
+}
+
 /// Information about sent transactions.
 #[pyclass(module = "solders.transaction_metadata", subclass)]
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
-pub struct TransactionMetadata(pub(crate) TransactionMetadataOriginal);
+#[serde(transparent)]
+pub struct TransactionMetadata {
+    pub(crate) inner: TransactionMetadataOriginal,
+    #[serde(skip)]
+    pub(crate) account_diffs: Option<Vec<AccountDiff>>,
+    #[serde(skip)]
+    pub(crate) fee_report: Option<FeeReport>,
+    #[serde(skip)]
+    pub(crate) traces: Option<Vec<ProgramTrace>>,
+    #[serde(skip)]
+    pub(crate) instruction_programs: Option<InstructionPrograms>,
+}
 
 transaction_status_boilerplate!(TransactionMetadata);
 
@@ -60,21 +502,21 @@ impl TransactionMetadata {
     /// Returns:
     ///      Signature: The transaction signature
     pub fn signature(&self) -> Signature {
-        Signature(self.0.signature)
+        Signature(self.inner.signature)
     }
 
     ///
     /// Returns:
     ///     list[str]: The transaction logs.
     pub fn logs(&self) -> Vec<String> {
-        self.0.logs.clone()
+        self.inner.logs.clone()
     }
 
     ///
     /// Returns:
     ///     list[list[InnerInstruction]]: The transaction's inner instructions.
     pub fn inner_instructions(&self) -> Vec<Vec<InnerInstruction>> {
-        self.0
+        self.inner
             .inner_instructions
             .clone()
             .into_iter()
@@ -86,21 +528,100 @@ impl TransactionMetadata {
     /// Returns:
     ///     int: The compute units consumed by the transaction.
     pub fn compute_units_consumed(&self) -> u64 {
-        self.0.compute_units_consumed
+        self.inner.compute_units_consumed
     }
 
     ///
     /// Returns:
     ///     TransactionReturnData: The transaction return data.
     pub fn return_data(&self) -> TransactionReturnData {
-        TransactionReturnData(self.0.return_data.clone())
+        TransactionReturnData(self.inner.return_data.clone())
+    }
+
+    /// Break down compute unit consumption per top-level instruction and per CPI,
+    /// using the ``Program X consumed N of M compute units`` logs and the inner instruction trace.
+    ///
+    /// Precompile instructions don't log their invocations, so they have no entry.
+    /// Invocations are matched to instructions by program, which is only known for
+    /// transactions sent or simulated by a ``LiteSVM`` instance. Otherwise, such as after
+    /// deserialization, they are matched in order, which precompiles can throw off.
+    ///
+    /// Returns:
+    ///     list[InstructionComputeUnits]: One tree per logged top-level invocation.
+    pub fn compute_units_profile(&self) -> Vec<InstructionComputeUnits> {
+        compute_units_profile(
+            &self.inner.logs,
+            &self.inner.inner_instructions,
+            self.instruction_programs.as_ref(),
+        )
+    }
+
+    /// Parse the logs into a tree of program invocations.
+    ///
+    /// Returns:
+    ///     ProgramLogs: The parsed logs.
+    pub fn parsed_logs(&self) -> ProgramLogs {
+        ProgramLogs::parse(&self.inner.logs)
+    }
+
+    /// The changes to the transaction's writable accounts.
+    /// Only recorded when account diffs are enabled on the ``LiteSVM`` instance,
+    /// and not preserved by serialization.
+    ///
+    /// Returns:
+    ///     Optional[list[AccountDiff]]: One entry per writable account, or None if not recorded.
+    pub fn account_diffs(&self) -> Option<Vec<AccountDiff>> {
+        self.account_diffs.clone()
+    }
+
+    /// The fees charged for the transaction.
+    /// Only recorded for transactions sent or simulated by a ``LiteSVM`` instance,
+    /// and not preserved by serialization.
+    ///
+    /// Returns:
+    ///     Optional[FeeReport]: The fee breakdown, or None if not recorded.
+    pub fn fee_report(&self) -> Option<FeeReport> {
+        self.fee_report.clone()
+    }
+
+    /// The SBF instructions executed by each invocation of a BPF program, in invocation order.
+    /// Only recorded when tracing is enabled on the ``LiteSVM`` instance,
+    /// and not preserved by serialization.
+    ///
+    /// Returns:
+    ///     Optional[list[ProgramTrace]]: One entry per program invocation, or None if not recorded.
+    pub fn traces(&self) -> Option<Vec<ProgramTrace>> {
+        self.traces.clone()
+    }
+}
+
+impl From<TransactionMetadataOriginal> for TransactionMetadata {
+    fn from(inner: TransactionMetadataOriginal) -> Self {
+        Self {
+            inner,
+            account_diffs: None,
+            fee_report: None,
+            traces: None,
+            instruction_programs: None,
+        }
     }
 }
 
 /// Information about failed transactions.
 #[pyclass(module = "solders.transaction_metadata", subclass)]
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
-pub struct FailedTransactionMetadata(pub(crate) FailedTransactionMetadataOriginal);
+#[serde(transparent)]
+pub struct FailedTransactionMetadata {
+    pub(crate) inner: FailedTransactionMetadataOriginal,
+    #[serde(skip)]
+    pub(crate) account_diffs: Option<Vec<AccountDiff>>,
+    #[serde(skip)]
+    pub(crate) fee_report: Option<FeeReport>,
+    #[serde(skip)]
+    pub(crate) traces: Option<Vec<ProgramTrace>>,
+    #[serde(skip)]
+    pub(crate) instruction_programs: Option<InstructionPrograms>,
+}
 
 transaction_status_boilerplate!(FailedTransactionMetadata);
 
@@ -112,21 +633,50 @@ impl FailedTransactionMetadata {
     /// Returns:
     ///     TransactionErrorType: The transaction error.
     pub fn err(&self) -> TransactionErrorType {
-        self.0.err.clone().into()
+        self.inner.err.clone().into()
     }
 
     ///
     /// Returns:
     ///     TransactionMetadata: The transaction metadata.
     pub fn meta(&self) -> TransactionMetadata {
-        TransactionMetadata(self.0.meta.clone())
+        TransactionMetadata {
+            inner: self.inner.meta.clone(),
+            account_diffs: self.account_diffs.clone(),
+            fee_report: self.fee_report.clone(),
+            traces: self.traces.clone(),
+            instruction_programs: self.instruction_programs.clone(),
+        }
+    }
+}
+
+impl From<FailedTransactionMetadataOriginal> for FailedTransactionMetadata {
+    fn from(inner: FailedTransactionMetadataOriginal) -> Self {
+        Self {
+            inner,
+            account_diffs: None,
+            fee_report: None,
+            traces: None,
+            instruction_programs: None,
+        }
     }
 }
 
 /// Information about simulated transactions.
 #[pyclass(module = "solders.transaction_metadata", subclass)]
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
-pub struct SimulatedTransactionInfo(pub(crate) SimulatedTransactionInfoOriginal);
+#[serde(transparent)]
+pub struct SimulatedTransactionInfo {
+    pub(crate) inner: SimulatedTransactionInfoOriginal,
+    #[serde(skip)]
+    pub(crate) account_diffs: Option<Vec<AccountDiff>>,
+    #[serde(skip)]
+    pub(crate) fee_report: Option<FeeReport>,
+    #[serde(skip)]
+    pub(crate) traces: Option<Vec<ProgramTrace>>,
+    #[serde(skip)]
+    pub(crate) instruction_programs: Option<InstructionPrograms>,
+}
 
 transaction_status_boilerplate!(SimulatedTransactionInfo);
 
@@ -138,14 +688,20 @@ impl SimulatedTransactionInfo {
     /// Returns:
     ///     TransactionMetadata: The transaction metadata.
     pub fn meta(&self) -> TransactionMetadata {
-        TransactionMetadata(self.0.meta.clone())
+        TransactionMetadata {
+            inner: self.inner.meta.clone(),
+            account_diffs: self.account_diffs.clone(),
+            fee_report: self.fee_report.clone(),
+            traces: self.traces.clone(),
+            instruction_programs: self.instruction_programs.clone(),
+        }
     }
 
     ///
     /// Returns:
     ///     list[tuple[Pubkey, Account]]: Pubkey-Account pairs, showing the state of writable accounts after transaction execution.
     pub fn post_accounts(&self) -> Vec<(Pubkey, Account)> {
-        self.0
+        self.inner
             .post_accounts
             .clone()
             .into_iter()
@@ -154,6 +710,18 @@ impl SimulatedTransactionInfo {
     }
 }
 
+impl From<SimulatedTransactionInfoOriginal> for SimulatedTransactionInfo {
+    fn from(inner: SimulatedTransactionInfoOriginal) -> Self {
+        Self {
+            inner,
+            account_diffs: None,
+            fee_report: None,
+            traces: None,
+            instruction_programs: None,
+        }
+    }
+}
+
 #[derive(FromPyObject, Clone, PartialEq, Debug, IntoPyObject)]
 pub enum TransactionResult {
     Ok(TransactionMetadata),
@@ -163,8 +731,8 @@ pub enum TransactionResult {
 impl From<TransactionResultOriginal> for TransactionResult {
     fn from(value: TransactionResultOriginal) -> Self {
         match value {
-            TransactionResultOriginal::Err(e) => Self::Err(FailedTransactionMetadata(e)),
-            TransactionResultOriginal::Ok(x) => Self::Ok(TransactionMetadata(x)),
+            TransactionResultOriginal::Err(e) => Self::Err(e.into()),
+            TransactionResultOriginal::Ok(x) => Self::Ok(x.into()),
         }
     }
 }
@@ -175,20 +743,92 @@ pub enum SimulateResult {
     Err(FailedTransactionMetadata),
 }
 
+impl TransactionResult {
+    pub(crate) fn with_account_diffs(mut self, diffs: Vec<AccountDiff>) -> Self {
+        match &mut self {
+            Self::Ok(x) => x.account_diffs = Some(diffs),
+            Self::Err(x) => x.account_diffs = Some(diffs),
+        }
+        self
+    }
+
+    pub(crate) fn with_fee_report(mut self, report: Option<FeeReport>) -> Self {
+        match &mut self {
+            Self::Ok(x) => x.fee_report = report,
+            Self::Err(x) => x.fee_report = report,
+        }
+        self
+    }
+
+    pub(crate) fn with_traces(mut self, traces: Option<Vec<ProgramTrace>>) -> Self {
+        match &mut self {
+            Self::Ok(x) => x.traces = traces,
+            Self::Err(x) => x.traces = traces,
+        }
+        self
+    }
+
+    pub(crate) fn with_instruction_programs(mut self, programs: InstructionPrograms) -> Self {
+        match &mut self {
+            Self::Ok(x) => x.instruction_programs = Some(programs),
+            Self::Err(x) => x.instruction_programs = Some(programs),
+        }
+        self
+    }
+}
+
 type SimResultOriginal =
     Result<SimulatedTransactionInfoOriginal, FailedTransactionMetadataOriginal>;
 
 impl From<SimResultOriginal> for SimulateResult {
     fn from(value: SimResultOriginal) -> Self {
         match value {
-            SimResultOriginal::Err(e) => Self::Err(FailedTransactionMetadata(e)),
-            SimResultOriginal::Ok(x) => Self::Ok(SimulatedTransactionInfo(x)),
+            SimResultOriginal::Err(e) => Self::Err(e.into()),
+            SimResultOriginal::Ok(x) => Self::Ok(x.into()),
+        }
+    }
+}
+
+impl SimulateResult {
+    pub(crate) fn with_account_diffs(mut self, diffs: Vec<AccountDiff>) -> Self {
+        match &mut self {
+            Self::Ok(x) => x.account_diffs = Some(diffs),
+            Self::Err(x) => x.account_diffs = Some(diffs),
+        }
+        self
+    }
+
+    pub(crate) fn with_fee_report(mut self, report: Option<FeeReport>) -> Self {
+        match &mut self {
+            Self::Ok(x) => x.fee_report = report,
+            Self::Err(x) => x.fee_report = report,
+        }
+        self
+    }
+
+    pub(crate) fn with_traces(mut self, traces: Option<Vec<ProgramTrace>>) -> Self {
+        match &mut self {
+            Self::Ok(x) => x.traces = traces,
+            Self::Err(x) => x.traces = traces,
+        }
+        self
+    }
+
+    pub(crate) fn with_instruction_programs(mut self, programs: InstructionPrograms) -> Self {
+        match &mut self {
+            Self::Ok(x) => x.instruction_programs = Some(programs),
+            Self::Err(x) => x.instruction_programs = Some(programs),
         }
+        self
     }
 }
 
 pub fn include_transaction_metadata(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<AccountDiff>()?;
+    m.add_class::<FeeReport>()?;
     m.add_class::<InnerInstruction>()?;
+    m.add_class::<InstructionComputeUnits>()?;
+    m.add_class::<ProgramTrace>()?;
     m.add_class::<TransactionMetadata>()?;
     m.add_class::<FailedTransactionMetadata>()?;
     m.add_class::<SimulatedTransactionInfo>()?;
@@ -0,0 +1,196 @@
+use std::str::FromStr;
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use solana_pubkey::Pubkey as PubkeyOriginal;
+use solders_macros::{common_methods, richcmp_eq_only};
+use solders_pubkey::Pubkey;
+use solders_traits_core::transaction_status_boilerplate;
+use solders_transaction_return_data::TransactionReturnData;
+
+const LOG_TRUNCATED: &str = "Log truncated";
+
+/// A single program invocation reconstructed from the transaction logs.
+#[pyclass(module = "solders.transaction_status", subclass)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProgramInvocation {
+    /// Pubkey: The invoked program.
+    #[pyo3(get)]
+    pub program_id: Pubkey,
+    /// int: Invocation stack height. Top-level instructions have depth 1.
+    #[pyo3(get)]
+    pub depth: u8,
+    /// list[str]: The ``Program log:`` messages emitted by this program, without the prefix.
+    #[pyo3(get)]
+    pub logs: Vec<String>,
+    /// list[list[bytes]]: The decoded ``Program data:`` payloads, one list per ``sol_log_data`` call.
+    #[pyo3(get)]
+    pub data: Vec<Vec<Vec<u8>>>,
+    /// Optional[int]: Compute units consumed, including CPIs. Builtin programs don't log this.
+    #[pyo3(get)]
+    pub consumed: Option<u64>,
+    /// Optional[int]: Compute units that were available when the invocation started.
+    #[pyo3(get)]
+    pub budget: Option<u64>,
+    /// Optional[TransactionReturnData]: The return data set by this program, if any.
+    #[pyo3(get)]
+    pub return_data: Option<TransactionReturnData>,
+    /// Optional[bool]: Whether the invocation succeeded. None if the logs were truncated before it finished.
+    #[pyo3(get)]
+    pub success: Option<bool>,
+    /// Optional[str]: The failure reason, if the invocation failed.
+    #[pyo3(get)]
+    pub error: Option<String>,
+    /// list[ProgramInvocation]: The invocations made via CPI.
+    #[pyo3(get)]
+    pub children: Vec<ProgramInvocation>,
+}
+
+transaction_status_boilerplate!(ProgramInvocation);
+
+#[richcmp_eq_only]
+#[common_methods]
+#[pymethods]
+impl ProgramInvocation {}
+
+impl ProgramInvocation {
+    fn new(program_id: PubkeyOriginal, depth: u8) -> Self {
+        Self {
+            program_id: Pubkey(program_id),
+            depth,
+            logs: Vec::new(),
+            data: Vec::new(),
+            consumed: None,
+            budget: None,
+            return_data: None,
+            success: None,
+            error: None,
+            children: Vec::new(),
+        }
+    }
+}
+
+/// Transaction logs parsed into a tree of program invocations.
+///
+/// Args:
+///     logs (Sequence[str]): The raw log messages.
+#[pyclass(module = "solders.transaction_status", subclass)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProgramLogs {
+    /// list[ProgramInvocation]: One tree per top-level instruction.
+    #[pyo3(get)]
+    pub invocations: Vec<ProgramInvocation>,
+    /// bool: Whether the logs were cut off by the log bytes limit.
+    #[pyo3(get)]
+    pub truncated: bool,
+}
+
+transaction_status_boilerplate!(ProgramLogs);
+
+#[richcmp_eq_only]
+#[common_methods]
+#[pymethods]
+impl ProgramLogs {
+    #[new]
+    pub fn new(logs: Vec<String>) -> Self {
+        Self::parse(&logs)
+    }
+}
+
+fn split_program_line(line: &str) -> Option<(PubkeyOriginal, &str)> {
+    let (program_id, rest) = line.strip_prefix("Program ")?.split_once(' ')?;
+    Some((PubkeyOriginal::from_str(program_id).ok()?, rest))
+}
+
+fn parse_consumed(rest: &str) -> Option<(u64, u64)> {
+    let counts = rest.strip_prefix("consumed ")?;
+    let (consumed, budget) = counts.strip_suffix(" compute units")?.split_once(" of ")?;
+    Some((consumed.parse().ok()?, budget.parse().ok()?))
+}
+
+fn decode_chunks(chunks: &str) -> Vec<Vec<u8>> {
+    chunks
+        .split(' ')
+        .filter_map(|chunk| base64::decode(chunk).ok())
+        .collect()
+}
+
+fn close_invocation(stack: &mut Vec<ProgramInvocation>, roots: &mut Vec<ProgramInvocation>) {
+    if let Some(node) = stack.pop() {
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => roots.push(node),
+        }
+    }
+}
+
+impl ProgramLogs {
+    pub fn parse(logs: &[String]) -> Self {
+        let mut roots = Vec::new();
+        let mut stack: Vec<ProgramInvocation> = Vec::new();
+        let mut truncated = false;
+        for line in logs {
+            if line == LOG_TRUNCATED {
+                truncated = true;
+                break;
+            }
+            if let Some(msg) = line.strip_prefix("Program log: ") {
+                if let Some(node) = stack.last_mut() {
+                    node.logs.push(msg.to_owned());
+                }
+            } else if let Some(chunks) = line.strip_prefix("Program data: ") {
+                if let Some(node) = stack.last_mut() {
+                    node.data.push(decode_chunks(chunks));
+                }
+            } else if let Some(rest) = line.strip_prefix("Program return: ") {
+                let parsed = rest.split_once(' ').and_then(|(program_id, data)| {
+                    Some(TransactionReturnData::new(
+                        Pubkey(PubkeyOriginal::from_str(program_id).ok()?),
+                        base64::decode(data).ok()?,
+                    ))
+                });
+                if let (Some(node), Some(return_data)) = (stack.last_mut(), parsed) {
+                    node.return_data = Some(return_data);
+                }
+            } else if let Some((program_id, rest)) = split_program_line(line) {
+                if let Some(depth) = rest
+                    .strip_prefix("invoke [")
+                    .and_then(|d| d.strip_suffix(']'))
+                    .and_then(|d| d.parse().ok())
+                {
+                    stack.push(ProgramInvocation::new(program_id, depth));
+                } else if let Some((consumed, budget)) = parse_consumed(rest) {
+                    if let Some(node) = stack.last_mut() {
+                        node.consumed = Some(consumed);
+                        node.budget = Some(budget);
+                    }
+                } else if rest == "success" {
+                    if let Some(node) = stack.last_mut() {
+                        node.success = Some(true);
+                    }
+                    close_invocation(&mut stack, &mut roots);
+                } else if let Some(reason) = rest.strip_prefix("failed: ") {
+                    if let Some(node) = stack.last_mut() {
+                        node.success = Some(false);
+                        node.error = Some(reason.to_owned());
+                    }
+                    close_invocation(&mut stack, &mut roots);
+                }
+            }
+        }
+        // Truncated logs leave invocations open.
+        while !stack.is_empty() {
+            close_invocation(&mut stack, &mut roots);
+        }
+        Self {
+            invocations: roots,
+            truncated,
+        }
+    }
+}
+
+pub fn include_program_logs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<ProgramInvocation>()?;
+    m.add_class::<ProgramLogs>()?;
+    Ok(())
+}
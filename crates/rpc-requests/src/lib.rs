@@ -10,7 +10,7 @@ use solders_message::VersionedMessage;
 use solders_pubkey::Pubkey;
 use solders_signature::Signature;
 use solders_traits_core::{
-    py_from_bytes_general_via_cbor, pybytes_general_via_cbor, RichcmpEqualityOnly,
+    py_from_bytes_general_via_cbor, pybytes_general_via_cbor, to_py_value_err, RichcmpEqualityOnly,
 };
 use solders_transaction::{Transaction, VersionedTransaction};
 
@@ -2796,6 +2796,36 @@ macro_rules ! pyunion {
             SimulateVersionedTransaction(SimulateVersionedTransaction),
         }
 
+        paste! {
+            /// Deserialization helper: a request's method alone can't tell the
+            /// transaction variants apart, so incoming transactions are read as versioned.
+            #[derive(Deserialize)]
+            #[serde(tag = "method", rename_all = "camelCase")]
+            enum [<$name Incoming>] {
+                $($variant($variant),)+
+                #[serde(rename = "sendTransaction")]
+                SendVersionedTransaction(SendVersionedTransaction),
+                #[serde(rename = "simulateTransaction")]
+                SimulateVersionedTransaction(SimulateVersionedTransaction),
+            }
+
+            impl<'de> Deserialize<'de> for $name {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    use [<$name Incoming>] as Incoming;
+                    Ok(match Incoming::deserialize(deserializer)? {
+                        Incoming::SendVersionedTransaction(x) => Self::SendVersionedTransaction(x),
+                        Incoming::SimulateVersionedTransaction(x) => {
+                            Self::SimulateVersionedTransaction(x)
+                        }
+                        $(Incoming::$variant(x) => Self::$variant(x),)+
+                    })
+                }
+            }
+        }
+
         impl<'py> IntoPyObject<'py> for $name {
             type Target = PyAny; // the Python type
             type Output = Bound<'py, Self::Target>; // in most cases this will be `Bound`
@@ -2893,6 +2923,16 @@ pub fn batch_requests_to_json(reqs: Vec<Body>) -> String {
     serde_json::to_string(&reqs).unwrap()
 }
 
+#[pyfunction]
+pub fn request_from_json(raw: &str) -> PyResult<Body> {
+    serde_json::from_str(raw).map_err(|e| to_py_value_err(&e))
+}
+
+#[pyfunction]
+pub fn batch_requests_from_json(raw: &str) -> PyResult<Vec<Body>> {
+    serde_json::from_str(raw).map_err(|e| to_py_value_err(&e))
+}
+
 pub fn include_requests(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<GetAccountInfo>()?;
     m.add_class::<GetBalance>()?;
@@ -2968,7 +3008,11 @@ pub fn include_requests(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<SlotsUpdatesUnsubscribe>()?;
     m.add_class::<RootUnsubscribe>()?;
     m.add_class::<VoteUnsubscribe>()?;
-    let funcs = [wrap_pyfunction!(batch_requests_to_json, m)?];
+    let funcs = [
+        wrap_pyfunction!(batch_requests_to_json, m)?,
+        wrap_pyfunction!(request_from_json, m)?,
+        wrap_pyfunction!(batch_requests_from_json, m)?,
+    ];
     for func in funcs {
         m.add_function(func)?;
     }
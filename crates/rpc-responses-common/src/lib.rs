@@ -54,9 +54,9 @@ macro_rules! contextful_struct_def_eq {
         #[pyclass(module = "solders.rpc.responses", subclass)]
         pub struct $name {
             #[pyo3(get)]
-            context: RpcResponseContext,
+            pub context: RpcResponseContext,
             #[pyo3(get)]
-            value: $inner,
+            pub value: $inner,
         }
     };
     ($name:ident, $inner:ty, $serde_as:expr) => {
@@ -65,10 +65,10 @@ macro_rules! contextful_struct_def_eq {
         #[pyclass(module = "solders.rpc.responses", subclass)]
         pub struct $name {
             #[pyo3(get)]
-            context: RpcResponseContext,
+            pub context: RpcResponseContext,
             #[pyo3(get)]
             #[serde_as(as = $serde_as)]
-            value: $inner,
+            pub value: $inner,
         }
     };
 }
@@ -80,9 +80,9 @@ macro_rules! contextful_struct_def_no_eq {
         #[pyclass(module = "solders.rpc.responses", subclass)]
         pub struct $name {
             #[pyo3(get)]
-            context: RpcResponseContext,
+            pub context: RpcResponseContext,
             #[pyo3(get)]
-            value: $inner,
+            pub value: $inner,
         }
     };
     ($name:ident, $inner:ty, $serde_as:expr) => {
@@ -91,10 +91,10 @@ macro_rules! contextful_struct_def_no_eq {
         #[pyclass(module = "solders.rpc.responses", subclass)]
         pub struct $name {
             #[pyo3(get)]
-            context: RpcResponseContext,
+            pub context: RpcResponseContext,
             #[pyo3(get)]
             #[serde_as(as = $serde_as)]
-            value: $inner,
+            pub value: $inner,
         }
     };
 }
@@ -151,7 +151,7 @@ macro_rules! contextless_struct_def_no_eq {
     ($name:ident, $inner:ty) => {
         #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
         #[pyclass(module = "solders.rpc.responses", subclass)]
-        pub struct $name($inner);
+        pub struct $name(pub $inner);
         resp_traits!($name);
     };
 }
@@ -160,14 +160,14 @@ macro_rules! contextless_struct_def_eq {
     ($name:ident, $inner:ty) => {
         #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
         #[pyclass(module = "solders.rpc.responses", subclass)]
-        pub struct $name($inner);
+        pub struct $name(pub $inner);
         resp_traits!($name);
     };
     ($name:ident, $inner:ty, $serde_as:expr) => {
         #[serde_as]
         #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
         #[pyclass(module = "solders.rpc.responses", subclass)]
-        pub struct $name(#[serde_as(as = $serde_as)] $inner);
+        pub struct $name(#[serde_as(as = $serde_as)] pub $inner);
         resp_traits!($name);
     };
 }
@@ -5,6 +5,7 @@ use pythonize::{depythonize, pythonize};
 use solders_account_decoder::UiTokenAmount;
 use solders_hash::Hash as SolderHash;
 use solders_message::MessageHeader;
+use solders_program_logs::{include_program_logs, ProgramLogs};
 use solders_pubkey::Pubkey;
 use solders_signature::Signature;
 use solders_traits_core::{
@@ -925,6 +926,13 @@ impl UiTransactionStatusMeta {
     pub fn log_messages(&self) -> Option<Vec<String>> {
         self.0.log_messages.clone().into()
     }
+    /// Parse ``log_messages`` into a tree of program invocations.
+    ///
+    /// Returns:
+    ///     Optional[ProgramLogs]: The parsed logs, or None if the log messages were omitted.
+    pub fn parsed_logs(&self) -> Option<ProgramLogs> {
+        self.log_messages().map(|logs| ProgramLogs::parse(&logs))
+    }
     #[getter]
     pub fn pre_token_balances(&self) -> Option<Vec<UiTransactionTokenBalance>> {
         let maybe_balances: Option<Vec<UiTransactionTokenBalanceOriginal>> =
@@ -1213,5 +1221,6 @@ pub fn include_transaction_status(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<TransactionStatus>()?;
     m.add_class::<EncodedConfirmedTransactionWithStatusMeta>()?;
     m.add_class::<UiConfirmedBlock>()?;
+    include_program_logs(m)?;
     Ok(())
 }
@@ -1,25 +1,48 @@
 """The Solana LiteSVM library."""
 from pathlib import Path
-from typing import List, Optional, Sequence, Tuple, Union
+from typing import (
+    Any,
+    Callable,
+    Dict,
+    Iterator,
+    List,
+    Optional,
+    Sequence,
+    Tuple,
+    Union,
+)
 
 from .solders import (
     Account,
+    AccountInfo,
     Clock,
     ComputeBudget,
     EpochRewards,
     EpochSchedule,
     FeatureSet,
     Hash,
+    Keypair,
+    LiteSVMSnapshot,
     Pubkey,
     Rent,
+    RpcConfirmedTransactionStatusWithSignature,
     Signature,
     SlotHistory,
     StakeHistory,
     Transaction,
+    TransactionStatus,
     VersionedTransaction,
 )
 from .solders import LiteSVM as _LiteSVM
+from .solders import (
+    GetAccountInfoResp,
+    GetMultipleAccountsResp,
+    GetProgramAccountsResp,
+    GetProgramAccountsWithContextResp,
+)
+from .rpc.filter import RpcFilterType
 from .transaction_metadata import SimulateResult, TransactionResult
+from .transaction_status import InstructionErrorType
 
 
 class LiteSVM:
@@ -28,9 +51,23 @@ class LiteSVM:
     Use this to send transactions, query accounts and configure the runtime.
     """
 
-    def __init__(self) -> None:
-        """Create a new LiteSVM instance with standard functionality enabled."""
-        inner = _LiteSVM()
+    def __init__(
+        self, feature_set: Optional[FeatureSet] = None, seed: Optional[int] = None
+    ) -> None:
+        """Create a new LiteSVM instance with standard functionality enabled.
+
+        Passing a ``seed`` makes runs reproducible: the first blockhash depends
+        on the seed, airdrops are paid by a keypair derived from the seed, and so
+        are the addresses returned by ``new_keypair()`` and ``create_mint()``.
+        Two instances with the same seed that send the same transactions produce
+        the same blockhashes, signatures and logs. Without a seed, every instance
+        starts from the same blockhash and airdrops are paid by a random keypair.
+
+        Args:
+            feature_set: The feature set to run under. Defaults to all features enabled.
+            seed: An unsigned 64-bit seed for the keys the instance creates.
+        """
+        inner = _LiteSVM(feature_set, seed)
         self._inner = inner
 
     @staticmethod
@@ -41,6 +78,33 @@ class LiteSVM:
         svm._inner = inner
         return svm
 
+    def save(self, path: Path) -> None:
+        """Write the current state to a file.
+
+        The file holds every account (including programs and sysvars),
+        the feature set, the latest blockhash and the seed, if any, along
+        with the number of keypairs derived from it. Runtime settings such
+        as sigverify and the transaction history are not saved.
+
+        Args:
+            path: The file to write to.
+        """
+        self._inner.save(path)
+
+    @staticmethod
+    def load(path: Path) -> "LiteSVM":
+        """Create a new LiteSVM instance from a file written by `save()`.
+
+        Args:
+            path: The file to read.
+
+        Returns:
+            The loaded LiteSVM instance
+        """
+        svm = LiteSVM.default()
+        svm._inner = _LiteSVM.load(path)
+        return svm
+
     def with_compute_budget(self, budget: ComputeBudget) -> "LiteSVM":
         """Set the compute budget.
 
@@ -53,6 +117,14 @@ class LiteSVM:
         self._inner.set_compute_budget(budget)
         return self
 
+    def get_compute_budget(self) -> Optional[ComputeBudget]:
+        """Get the compute budget, if one was set.
+
+        Returns:
+            The compute budget.
+        """
+        return self._inner.get_compute_budget()
+
     def with_sigverify(self, sigverify: bool) -> "LiteSVM":
         """Enable or disable sigverify.
 
@@ -77,6 +149,23 @@ class LiteSVM:
         self._inner.set_blockhash_check(check)
         return self
 
+    def with_feature_set(self, feature_set: FeatureSet) -> "LiteSVM":
+        """Change the feature set and rebuild everything that depends on it.
+
+        Builtins and precompiles are re-added and the compute budget,
+        if one was set, is updated to match the new features. Builtins and
+        precompiles that the new feature set disables are removed, unless a
+        program or mock program was installed at their address.
+
+        Args:
+            feature_set: The new feature set.
+
+        Returns:
+            The modified LiteSVM instance
+        """
+        self._inner.set_feature_set(feature_set)
+        return self
+
     def with_sysvars(self) -> "LiteSVM":
         """Sets up the standard sysvars.
 
@@ -146,6 +235,93 @@ class LiteSVM:
         self._inner.set_log_bytes_limit(limit)
         return self
 
+    def with_auto_advance(
+        self, enabled: bool = True, slot_duration_ms: int = 400
+    ) -> "LiteSVM":
+        """Move to the next slot after each ``send_transaction``.
+
+        Each sent transaction, successful or not, advances the slot by one,
+        moves ``Clock.unix_timestamp`` forward by ``slot_duration_ms``,
+        rotates the latest blockhash and records the slot in ``SlotHashes``
+        and ``SlotHistory``. Transactions must therefore be built with the
+        blockhash that is current when they are sent.
+        A batch sent with ``send_transactions`` lands in a single slot.
+
+        Args:
+            enabled: Whether to advance automatically.
+            slot_duration_ms: Wall-clock time per slot, in milliseconds.
+
+        Returns:
+            The modified LiteSVM instance
+        """
+        self._inner.set_auto_advance(enabled, slot_duration_ms)
+        return self
+
+    def with_account_diffs(self, enabled: bool = True) -> "LiteSVM":
+        """Record how each writable account changes in transaction results.
+
+        When enabled, ``TransactionMetadata.account_diffs()`` returns one
+        ``AccountDiff`` per writable account for every sent or simulated transaction.
+        Failed simulations don't record diffs.
+
+        Args:
+            enabled: Whether to record account diffs.
+
+        Returns:
+            The modified LiteSVM instance
+        """
+        self._inner.set_account_diffs(enabled)
+        return self
+
+    def with_tracing(self, enabled: bool = True) -> "LiteSVM":
+        """Trace the SBF instructions executed by BPF programs.
+
+        When enabled, ``TransactionMetadata.traces()`` returns a ``ProgramTrace``
+        for each invocation of a program owned by one of the BPF loaders,
+        including CPIs, for every sent or simulated transaction. The
+        instructions executed by sent transactions also count towards
+        ``coverage()``. Traced programs are loaded again for each transaction
+        and record every instruction, so transactions are slower and use more
+        memory.
+
+        Args:
+            enabled: Whether to trace programs.
+
+        Returns:
+            The modified LiteSVM instance
+        """
+        self._inner.set_tracing(enabled)
+        return self
+
+    def coverage(self) -> Dict[Pubkey, Dict[int, int]]:
+        """Count how often sent transactions executed each instruction of each program.
+
+        Only transactions sent while tracing is enabled are counted.
+        See ``with_tracing``.
+
+        Returns:
+            For each traced program, a mapping of program counter to hit count.
+            Instructions that never ran have a count of zero.
+        """
+        return self._inner.coverage()
+
+    def coverage_report(self) -> str:
+        """Dump ``coverage()`` as text.
+
+        The report has one ``<program id> <pc> <hits>`` line per instruction,
+        sorted by program and program counter. Program counters are
+        instruction indices in the program's text section, not source lines:
+        DWARF debug info is not used.
+
+        Returns:
+            The report.
+        """
+        return self._inner.coverage_report()
+
+    def reset_coverage(self) -> None:
+        """Discard the coverage counted so far."""
+        self._inner.reset_coverage()
+
     def minimum_balance_for_rent_exemption(self, data_len: int) -> int:
         """Calculates the minimum balance required to make an account rent exempt.
 
@@ -170,6 +346,29 @@ class LiteSVM:
         """
         return self._inner.get_account(address)
 
+    def accounts(self) -> Iterator[Tuple[Pubkey, Account]]:
+        """Iterate over every account, including programs and sysvars.
+
+        Returns:
+            (address, account) pairs, sorted by address.
+        """
+        return iter(self._inner.accounts())
+
+    def get_program_accounts(
+        self, program_id: Pubkey, filters: Optional[Sequence[RpcFilterType]] = None
+    ) -> List[Tuple[Pubkey, Account]]:
+        """Return the accounts owned by a program, like ``getProgramAccounts``.
+
+        Args:
+            program_id: The owner program.
+            filters: Data size, ``Memcmp`` or ``TokenAccountState`` filters.
+                An account must pass all of them.
+
+        Returns:
+            (address, account) pairs, sorted by address.
+        """
+        return self._inner.get_program_accounts(program_id, filters)
+
     def set_account(self, address: Pubkey, account: Account) -> None:
         """Create or overwrite an account, subverting normal runtime checks.
 
@@ -184,6 +383,147 @@ class LiteSVM:
         """
         self._inner.set_account(address, account)
 
+    def load_account_from_json(self, path: Path) -> Pubkey:
+        """Add an account from a JSON dump.
+
+        The file uses the format written by ``solana account --output json``
+        and read by ``solana-test-validator --account``.
+
+        Args:
+            path: The JSON file.
+
+        Returns:
+            The address of the loaded account.
+        """
+        return self._inner.load_account_from_json(path)
+
+    def load_accounts_from_dir(self, path: Path) -> List[Pubkey]:
+        """Add every ``.json`` account dump in a directory.
+
+        This mirrors ``solana-test-validator --account-dir``.
+
+        Args:
+            path: The directory to read.
+
+        Returns:
+            The addresses of the loaded accounts.
+        """
+        return self._inner.load_accounts_from_dir(path)
+
+    def add_accounts_from_rpc_response(
+        self,
+        resp: Union[
+            GetAccountInfoResp,
+            GetMultipleAccountsResp,
+            GetProgramAccountsResp,
+            GetProgramAccountsWithContextResp,
+        ],
+        addresses: Optional[Sequence[Pubkey]] = None,
+    ) -> List[Pubkey]:
+        """Install the accounts from a recorded RPC response.
+
+        This lets you reproduce mainnet state offline, e.g. with
+        ``GetMultipleAccountsResp.from_json(path.read_text())``.
+        Programs owned by the upgradeable BPF loader are installed after
+        their ProgramData accounts so that they become executable.
+
+        Args:
+            resp: The parsed RPC response. Only base64-encoded accounts are supported.
+            addresses: The account addresses, in request order. Required for
+                ``getAccountInfo`` and ``getMultipleAccounts`` responses,
+                which don't include them.
+
+        Returns:
+            The addresses of the installed accounts.
+            Accounts that were missing from the response are skipped.
+        """
+        return self._inner.add_accounts_from_rpc_response(resp, addresses)
+
+    def save_account_to_json(self, address: Pubkey, path: Path) -> None:
+        """Write an account in the ``solana account --output json`` format.
+
+        Args:
+            address: The account address.
+            path: The file to write to.
+        """
+        self._inner.save_account_to_json(address, path)
+
+    def save_accounts_to_dir(self, addresses: Sequence[Pubkey], path: Path) -> None:
+        """Write each account to ``<address>.json`` in the given directory.
+
+        The directory can be passed to `load_accounts_from_dir()`
+        or to ``solana-test-validator --account-dir``.
+
+        Args:
+            addresses: The account addresses.
+            path: The directory to write to. Created if missing.
+        """
+        self._inner.save_accounts_to_dir(addresses, path)
+
+    def create_mint(
+        self,
+        authority: Pubkey,
+        decimals: int,
+        mint: Optional[Pubkey] = None,
+        freeze_authority: Optional[Pubkey] = None,
+        token_program_id: Optional[Pubkey] = None,
+    ) -> Pubkey:
+        """Writes an initialized, rent-exempt mint account with zero supply.
+
+        Args:
+            authority: The mint authority.
+            decimals: The number of decimals.
+            mint: The mint address. Defaults to a new unique address,
+                which is derived from the seed if the instance has one.
+            freeze_authority: The optional freeze authority.
+            token_program_id: The Token or Token-2022 program ID.
+                Defaults to the Token program.
+
+        Returns:
+            The mint address.
+        """
+        return self._inner.create_mint(
+            authority, decimals, mint, freeze_authority, token_program_id
+        )
+
+    def create_ata(self, owner: Pubkey, mint: Pubkey) -> Pubkey:
+        """Writes an empty, rent-exempt associated token account.
+
+        The token program is taken from the mint's owner. If the associated
+        token account already exists, it is left as is.
+
+        Args:
+            owner: The wallet that owns the token account.
+            mint: The token mint.
+
+        Returns:
+            The associated token account address.
+        """
+        return self._inner.create_ata(owner, mint)
+
+    def mint_to(self, mint: Pubkey, destination: Pubkey, amount: int) -> None:
+        """Adds tokens to a token account and to the mint's supply.
+
+        No mint authority signature is needed.
+
+        Args:
+            mint: The token mint.
+            destination: The token account to credit.
+            amount: The number of tokens, in base units.
+        """
+        self._inner.mint_to(mint, destination, amount)
+
+    def set_token_balance(self, account: Pubkey, amount: int) -> None:
+        """Sets the amount held by a token account.
+
+        The mint supply is adjusted by the difference, so it stays consistent.
+
+        Args:
+            account: The token account.
+            amount: The new amount, in base units.
+        """
+        self._inner.set_token_balance(account, amount)
+
     def get_balance(self, address: Pubkey) -> Optional[int]:
         """Gets the balance of the provided account address.
 
@@ -229,6 +569,84 @@ class LiteSVM:
         """
         return self._inner.airdrop(address, lamports)
 
+    def new_keypair(self) -> Keypair:
+        """Creates a keypair.
+
+        If the instance was created with a ``seed``, the keypair is derived from
+        the seed and the number of keypairs created so far. Otherwise it is random.
+
+        Returns:
+            The new keypair.
+        """
+        return self._inner.new_keypair()
+
+    def get_signatures_for_address(
+        self,
+        address: Pubkey,
+        before: Optional[Signature] = None,
+        until: Optional[Signature] = None,
+        limit: Optional[int] = None,
+    ) -> List[RpcConfirmedTransactionStatusWithSignature]:
+        """Finds the transactions in the transaction history that reference an address.
+
+        This mirrors the ``getSignaturesForAddress`` RPC method.
+
+        Args:
+            address: The account address.
+            before: Only return signatures older than this one.
+            until: Only return signatures newer than this one.
+            limit: The maximum number of signatures to return. Defaults to 1000,
+                which is also the upper bound.
+
+        Returns:
+            The matching signatures, newest first.
+        """
+        return self._inner.get_signatures_for_address(address, before, until, limit)
+
+    def get_signature_statuses(
+        self, signatures: Sequence[Signature]
+    ) -> List[Optional[TransactionStatus]]:
+        """Looks up the status of transactions in the transaction history.
+
+        This mirrors the ``getSignatureStatuses`` RPC method.
+
+        Args:
+            signatures: The transaction signatures.
+
+        Returns:
+            The status of each signature, or None if it isn't in the history.
+        """
+        return self._inner.get_signature_statuses(signatures)
+
+    def add_mock_program(
+        self,
+        program_id: Pubkey,
+        process_instruction: Callable[
+            [Pubkey, List[AccountInfo], bytes],
+            Union[None, bytes, InstructionErrorType],
+        ],
+    ) -> None:
+        """Adds a program implemented by a Python callable.
+
+        This stands in for programs whose ELF isn't available, such as an oracle.
+        The mock can be called by top-level instructions and via CPI.
+
+        ``process_instruction`` is called with the program ID, an ``AccountInfo``
+        for each instruction account and the instruction data. It can change the
+        ``lamports``, ``data`` and ``owner`` of the accounts, subject to the usual
+        runtime checks. It returns None on success, ``bytes`` to succeed and set
+        return data, or an ``InstructionErrorType`` to fail. If it raises an
+        exception, the instruction fails with ``ProgramFailedToComplete`` and the
+        exception is written to the logs.
+
+        The callable must not use this ``LiteSVM`` instance.
+
+        Args:
+            program_id: The program ID.
+            process_instruction: The program implementation.
+        """
+        self._inner.add_mock_program(program_id, process_instruction)
+
     def add_program_from_file(self, program_id: Pubkey, path: Path) -> None:
         """Adds an SBF program to the test environment from the file specified.
 
@@ -247,6 +665,94 @@ class LiteSVM:
         """
         return self._inner.add_program(program_id, program_bytes)
 
+    def add_upgradeable_program(
+        self,
+        program_id: Pubkey,
+        program_bytes: bytes,
+        upgrade_authority: Optional[Pubkey] = None,
+    ) -> None:
+        """Deploys an SBF program through the upgradeable BPF loader.
+
+        Unlike `add_program()`, this creates the Program and ProgramData accounts
+        that the loader would create, so programs that inspect their ProgramData
+        or upgrade authority behave as they do on a real cluster.
+
+        Args:
+            program_id: The program ID.
+            program_bytes: The raw bytes of the compiled program.
+            upgrade_authority: The upgrade authority. If None, the program is immutable.
+        """
+        self._inner.add_upgradeable_program(
+            program_id, program_bytes, upgrade_authority
+        )
+
+    def upgrade_program(
+        self,
+        program_id: Pubkey,
+        program_bytes: bytes,
+        upgrade_authority: Keypair,
+        payer: Optional[Keypair] = None,
+    ) -> TransactionResult:
+        """Upgrades a program deployed with `add_upgradeable_program()`.
+
+        This writes the new program to a buffer account and then sends
+        a real loader ``Upgrade`` instruction, so the authority checks
+        are the ones the loader performs. If the new program is larger,
+        the ProgramData account is first extended in a separate transaction
+        and the slot is advanced by one. The transactions are processed like
+        `send_transaction()`, and if one of them fails, the buffer account
+        is removed.
+
+        As on a real cluster, a program can't be upgraded in the slot
+        it was deployed in, so call `warp_to_slot()` after deploying.
+
+        Args:
+            program_id: The program ID.
+            program_bytes: The raw bytes of the new program.
+            upgrade_authority: The current upgrade authority.
+            payer: Pays the transaction fee and receives the buffer's lamports.
+                Defaults to ``upgrade_authority``.
+
+        Returns:
+            The result of the upgrade transaction.
+
+        Raises:
+            ValueError: If the ProgramData account would have to grow
+                by more than ``2**32 - 1`` bytes.
+        """
+        return self._inner.upgrade_program(
+            program_id, program_bytes, upgrade_authority, payer
+        )
+
+    def create_lookup_table(
+        self,
+        authority: Keypair,
+        addresses: Sequence[Pubkey],
+        payer: Optional[Keypair] = None,
+    ) -> Pubkey:
+        """Creates an address lookup table, extends it and warms it up.
+
+        The table is created with the Address Lookup Table program, using the
+        most recent slot in ``SlotHashes``, then extended with ``addresses`` over
+        as many transactions as needed. Addresses can't be loaded in the slot
+        they were added in, so this finishes by moving to the next slot.
+        The transactions are processed like ``send_transaction``, so they are
+        recorded in the transaction history and trigger callbacks.
+
+        Args:
+            authority: The table authority, which signs the extend transactions.
+            addresses: The addresses to store in the table.
+            payer: Pays for the transactions and the table's rent.
+                Defaults to ``authority``.
+
+        Returns:
+            The lookup table address.
+
+        Raises:
+            ValueError: If one of the transactions fails.
+        """
+        return self._inner.create_lookup_table(authority, addresses, payer)
+
     def send_transaction(
         self, tx: Union[Transaction, VersionedTransaction]
     ) -> TransactionResult:
@@ -260,6 +766,58 @@ class LiteSVM:
         """
         return self._inner.send_transaction(tx)
 
+    def send_transactions(
+        self,
+        txs: Sequence[Union[Transaction, VersionedTransaction]],
+        atomic: bool = False,
+    ) -> List[TransactionResult]:
+        """Processes transactions in order, like a bundle landing in a single slot.
+
+        With ``atomic=True``, processing stops at the first failed transaction
+        and every change made by the batch, including fees, is rolled back.
+        The transaction history and callbacks only see batches that are kept.
+
+        Args:
+            txs: The transactions to send.
+            atomic: Whether the batch succeeds or fails as a unit.
+
+        Returns:
+            The result of each processed transaction. If an atomic batch fails,
+            this ends with the failed transaction.
+        """
+        return self._inner.send_transactions(txs, atomic)
+
+    @staticmethod
+    def send_transactions_parallel(
+        jobs: Sequence[
+            Tuple["LiteSVM", Sequence[Union[Transaction, VersionedTransaction]]]
+        ],
+        atomic: bool = False,
+        max_workers: Optional[int] = None,
+    ) -> List[List[TransactionResult]]:
+        """Runs `send_transactions()` for several instances at once.
+
+        The jobs are split across up to ``max_workers`` threads, which run with
+        the GIL released. Mock programs take the GIL while they run, and callbacks
+        run on the calling thread after every job has finished.
+
+        Args:
+            jobs: ``(instance, transactions)`` pairs. Each job needs its own instance,
+                for example one made with `fork()`.
+            atomic: Whether each job's transactions succeed or fail as a unit.
+            max_workers: The maximum number of threads.
+                Defaults to the number of available CPUs.
+
+        Returns:
+            The results of each job, in the same order as ``jobs``.
+
+        Raises:
+            ValueError: If two jobs share an instance.
+        """
+        return _LiteSVM.send_transactions_parallel(
+            [(svm._inner, txs) for svm, txs in jobs], atomic, max_workers
+        )
+
     def simulate_transaction(
         self, tx: Union[Transaction, VersionedTransaction]
     ) -> SimulateResult:
@@ -273,6 +831,74 @@ class LiteSVM:
         """
         return self._inner.simulate_transaction(tx)
 
+    def on_transaction(
+        self,
+        callback: Callable[[TransactionResult, List[Tuple[Pubkey, Account]]], Any],
+    ) -> None:
+        """Register a callback to run after each ``send_transaction``.
+
+        The callback gets the transaction result and the new state of every
+        account the transaction modified, and may inspect this LiteSVM instance.
+        Exceptions raised by the callback propagate out of ``send_transaction``,
+        after the transaction has been processed.
+
+        Args:
+            callback: Called as ``callback(result, changed_accounts)``, where
+                ``changed_accounts`` is a list of (address, account) pairs.
+        """
+        self._inner.on_transaction(callback)
+
+    def on_account_change(
+        self, pubkey: Pubkey, callback: Callable[[Pubkey, Account], Any]
+    ) -> None:
+        """Register a callback to run when a sent transaction modifies an account.
+
+        Exceptions raised by the callback propagate out of ``send_transaction``,
+        after the transaction has been processed.
+
+        Args:
+            pubkey: The account to watch.
+            callback: Called as ``callback(pubkey, account)`` with the new state.
+        """
+        self._inner.on_account_change(pubkey, callback)
+
+    def fork(self) -> "LiteSVM":
+        """Create an independent copy of this instance.
+
+        The copy has the same accounts, sysvars, blockhash, transaction history,
+        feature set and settings, but no callbacks, and its ``coverage()``
+        starts empty. Account data is shared until one of the instances
+        writes to it, so forking is cheap.
+
+        Returns:
+            The new instance.
+        """
+        svm = LiteSVM.default()
+        svm._inner = self._inner.fork()
+        return svm
+
+    def snapshot(self) -> LiteSVMSnapshot:
+        """Take a snapshot of the current state.
+
+        The snapshot covers accounts, programs, sysvars, the latest blockhash,
+        the transaction history, ``coverage()`` and the position in the
+        sequence of keypairs derived from the seed.
+
+        Returns:
+            An opaque handle that can be passed to `restore()`.
+        """
+        return self._inner.snapshot()
+
+    def restore(self, snapshot: LiteSVMSnapshot) -> None:
+        """Roll back to a previously taken snapshot.
+
+        The same snapshot can be restored any number of times.
+
+        Args:
+            snapshot: The snapshot returned by `snapshot()`.
+        """
+        self._inner.restore(snapshot)
+
     def expire_blockhash(self) -> None:
         """Expires the current blockhash.
 
@@ -283,13 +909,40 @@ class LiteSVM:
     def warp_to_slot(self, slot: int) -> None:
         """Warps the clock to the specified slot.
 
-        This is a convenience wrapper around `set_clock()`.
+        Warping forward records the current slot in ``SlotHashes`` and the new
+        slot in ``SlotHistory``, as if the slots in between had been skipped.
+        This is what address lookup tables check their deactivation against.
 
         Args:
             slot: The new slot.
         """
         self._inner.warp_to_slot(slot)
 
+    def warp_to_epoch(self, epoch: int, reward_rate: Optional[float] = None) -> None:
+        """Warps to the start of ``epoch``, crossing each epoch boundary on the way.
+
+        At every boundary the ending epoch's effective, activating and deactivating
+        stake is recorded in ``StakeHistory``, which is what the stake program uses
+        to warm up and cool down delegations. ``Clock.epoch``,
+        ``Clock.leader_schedule_epoch`` and ``Clock.epoch_start_timestamp`` are
+        updated to match.
+
+        If ``reward_rate`` is given, each stake account also earns
+        ``reward_rate * effective_stake`` lamports per epoch. The rewards are added
+        to the delegation, as on a real cluster, and summarized in ``EpochRewards``.
+        Vote account commission and vote credits are not taken into account.
+
+        Args:
+            epoch: The epoch to warp to. Must be later than the current epoch.
+            reward_rate: The fraction of effective stake paid out per epoch,
+                between 0 and 1.
+
+        Raises:
+            ValueError: If ``epoch`` is not later than the current epoch
+                or ``reward_rate`` is not between 0 and 1.
+        """
+        self._inner.warp_to_epoch(epoch, reward_rate)
+
     def get_clock(self) -> Clock:
         """Get the cluster clock.
 
@@ -419,4 +1072,4 @@ class LiteSVM:
         self._inner.set_stake_history(history)
 
 
-__all__ = ["FeatureSet", "LiteSVM"]
+__all__ = ["AccountInfo", "FeatureSet", "LiteSVM", "LiteSVMSnapshot"]
@@ -0,0 +1,461 @@
+"""Randomised property testing of programs running in LiteSVM.
+
+A :class:`Fuzzer` sends random sequences of :class:`Action` instructions to a
+``LiteSVM`` instance, starting each sequence from the same snapshot.
+Failed transactions, exceptions and invariant violations are reported as a
+:class:`FuzzFailure`, shrunk to a minimal failing sequence.
+"""
+import random
+from dataclasses import dataclass, field
+from typing import (
+    Any,
+    Callable,
+    Iterator,
+    List,
+    Mapping,
+    Optional,
+    Sequence,
+    Tuple,
+    Union,
+)
+
+from .instruction import Instruction
+from .keypair import Keypair
+from .litesvm import LiteSVM
+from .message import Message
+from .solders import LiteSVMSnapshot
+from .transaction import VersionedTransaction
+from .transaction_metadata import FailedTransactionMetadata, TransactionResult
+from .transaction_status import (
+    InstructionErrorType,
+    TransactionErrorInstructionError,
+    TransactionErrorType,
+)
+
+PANIC = "panic"
+TRANSACTION_ERROR = "transaction_error"
+INVARIANT = "invariant"
+
+
+class Strategy:
+    """Draws random values for an action argument and proposes simpler ones."""
+
+    def draw(self, rng: random.Random) -> Any:
+        """Draws a random value.
+
+        Args:
+            rng: The random number generator to use.
+
+        Returns:
+            The drawn value.
+        """
+        raise NotImplementedError
+
+    def shrink(self, value: Any) -> Iterator[Any]:
+        """Proposes simpler versions of a drawn value, simplest first.
+
+        Args:
+            value: A value returned by ``draw``.
+
+        Yields:
+            The candidate values.
+        """
+        return iter(())
+
+
+class _Integers(Strategy):
+    def __init__(self, min_value: int, max_value: int) -> None:
+        if min_value > max_value:
+            raise ValueError("min_value must not be greater than max_value")
+        self.min_value = min_value
+        self.max_value = max_value
+
+    def draw(self, rng: random.Random) -> int:
+        return rng.randint(self.min_value, self.max_value)
+
+    def shrink(self, value: int) -> Iterator[int]:
+        target = min(max(0, self.min_value), self.max_value)
+        diff = value - target
+        while diff != 0:
+            yield value - diff
+            diff = int(diff / 2)
+
+
+class _SampledFrom(Strategy):
+    def __init__(self, values: Sequence[Any]) -> None:
+        if not values:
+            raise ValueError("Cannot sample from an empty sequence")
+        self.values = list(values)
+
+    def draw(self, rng: random.Random) -> Any:
+        return rng.choice(self.values)
+
+    def shrink(self, value: Any) -> Iterator[Any]:
+        for candidate in self.values:
+            if candidate == value:
+                return
+            yield candidate
+
+
+class _Binary(Strategy):
+    def __init__(self, min_size: int, max_size: int) -> None:
+        if min_size > max_size:
+            raise ValueError("min_size must not be greater than max_size")
+        self.min_size = min_size
+        self.max_size = max_size
+
+    def draw(self, rng: random.Random) -> bytes:
+        size = rng.randint(self.min_size, self.max_size)
+        return bytes(rng.getrandbits(8) for _ in range(size))
+
+    def shrink(self, value: bytes) -> Iterator[bytes]:
+        size = self.min_size
+        while size < len(value):
+            yield value[:size]
+            size += max(1, (len(value) - size) // 2)
+        if any(value):
+            yield bytes(len(value))
+
+
+def integers(min_value: int, max_value: int) -> Strategy:
+    """Integers between ``min_value`` and ``max_value``, inclusive.
+
+    Shrinks towards zero, or the bound closest to zero.
+
+    Args:
+        min_value: The smallest value.
+        max_value: The largest value.
+
+    Returns:
+        The strategy.
+    """
+    return _Integers(min_value, max_value)
+
+
+def sampled_from(values: Sequence[Any]) -> Strategy:
+    """Elements of ``values``, such as the accounts an instruction may use.
+
+    Shrinks towards the first element.
+
+    Args:
+        values: The values to choose from.
+
+    Returns:
+        The strategy.
+    """
+    return _SampledFrom(values)
+
+
+def binary(min_size: int = 0, max_size: int = 64) -> Strategy:
+    """Random bytes, such as raw instruction data.
+
+    Shrinks towards shorter strings of zero bytes.
+
+    Args:
+        min_size: The smallest length.
+        max_size: The largest length.
+
+    Returns:
+        The strategy.
+    """
+    return _Binary(min_size, max_size)
+
+
+@dataclass(eq=False)
+class Action:
+    """An instruction generator.
+
+    Each time the action is chosen, an argument is drawn from each strategy in
+    ``args`` and passed to ``build`` by name.
+
+    Attributes:
+        name: The name used in failure reports.
+        build: Returns the instruction, or instructions, to send in one transaction.
+        args: The strategy for each keyword argument of ``build``.
+        signers: Keypairs that the built instructions may require,
+            in addition to the fuzzer's payer.
+    """
+
+    name: str
+    build: Callable[..., Union[Instruction, Sequence[Instruction]]]
+    args: Mapping[str, Strategy] = field(default_factory=dict)
+    signers: Sequence[Keypair] = ()
+
+
+@dataclass(frozen=True)
+class Step:
+    """One transaction in a fuzzed sequence.
+
+    Attributes:
+        action: The action that built the transaction.
+        args: The arguments drawn for the action.
+    """
+
+    action: Action
+    args: Mapping[str, Any]
+
+    def __str__(self) -> str:
+        """The action call, as ``name(arg=value, ...)``."""
+        args = ", ".join(f"{name}={value!r}" for name, value in self.args.items())
+        return f"{self.action.name}({args})"
+
+
+@dataclass
+class _Outcome:
+    kind: str
+    result: Optional[TransactionResult] = None
+    exception: Optional[BaseException] = None
+
+    def matches(self, other: "_Outcome") -> bool:
+        if self.kind != other.kind:
+            return False
+        if self.kind == TRANSACTION_ERROR:
+            return _err(self.result) == _err(other.result)
+        return type(self.exception) is type(other.exception)
+
+
+def _err(result: Optional[TransactionResult]) -> Optional[TransactionErrorType]:
+    if isinstance(result, FailedTransactionMetadata):
+        return result.err()
+    return None
+
+
+@dataclass
+class FuzzFailure:
+    """A failing sequence found by :meth:`Fuzzer.run`.
+
+    Attributes:
+        kind: ``"panic"`` if an action or the runtime raised an exception,
+            ``"transaction_error"`` if a transaction failed,
+            or ``"invariant"`` if an invariant hook raised an exception.
+        steps: The shrunk sequence. The last step is the one that failed.
+        original_steps: The sequence as first found, up to the failing step.
+        result: The result of the last transaction sent, if any.
+        exception: The exception raised, for panics and invariant violations.
+        seed: The seed that produced the original sequence.
+    """
+
+    kind: str
+    steps: List[Step]
+    original_steps: List[Step]
+    result: Optional[TransactionResult]
+    exception: Optional[BaseException]
+    seed: int
+
+    @property
+    def error(self) -> Optional[TransactionErrorType]:
+        """The error of the last transaction, if it failed."""
+        return _err(self.result)
+
+    @property
+    def instruction_error(self) -> Optional[InstructionErrorType]:
+        """The instruction error of the last transaction, if it failed with one."""
+        err = self.error
+        if isinstance(err, TransactionErrorInstructionError):
+            return err.err
+        return None
+
+    def __str__(self) -> str:
+        """A readable report of the failure and the shrunk sequence."""
+        if self.kind == TRANSACTION_ERROR:
+            reason = f"transaction failed: {self.error}"
+        else:
+            reason = f"{self.kind}: {self.exception!r}"
+        lines = [
+            f"{reason} after {len(self.steps)} step(s), "
+            f"shrunk from {len(self.original_steps)} (seed {self.seed})",
+        ]
+        lines.extend(f"  {num}. {step}" for num, step in enumerate(self.steps, 1))
+        return "\n".join(lines)
+
+
+class Fuzzer:
+    """Sends random sequences of actions and shrinks the ones that fail.
+
+    Every sequence starts from the state ``svm`` was in when ``run`` was called,
+    and ``svm`` is restored to that state afterwards.
+    """
+
+    def __init__(
+        self,
+        svm: LiteSVM,
+        payer: Keypair,
+        actions: Sequence[Action],
+        invariants: Sequence[Callable[[LiteSVM], None]] = (),
+        expected_error: Optional[Callable[[Step, TransactionErrorType], bool]] = None,
+        seed: Optional[int] = None,
+        max_shrinks: int = 1000,
+    ) -> None:
+        """Create a fuzzer.
+
+        Args:
+            svm: The instance to fuzz.
+            payer: Pays the fee of every transaction.
+            actions: The actions to choose from.
+            invariants: Hooks called with ``svm`` after each transaction.
+                Raising an exception, such as with ``assert``, reports a violation.
+            expected_error: Returns True for transaction errors that are not bugs,
+                such as an action rejecting bad input. By default,
+                every failed transaction is reported.
+            seed: Seeds the random sequences. Random if None.
+            max_shrinks: The maximum number of sequences to replay while shrinking.
+        """
+        if not actions:
+            raise ValueError("At least one action is required")
+        self.svm = svm
+        self.payer = payer
+        self.actions = list(actions)
+        self.invariants = list(invariants)
+        self.expected_error = expected_error
+        self.seed = random.randrange(2**32) if seed is None else seed
+        self.max_shrinks = max_shrinks
+
+    def run(self, iterations: int = 100, max_steps: int = 10) -> Optional[FuzzFailure]:
+        """Sends random sequences until one fails.
+
+        Args:
+            iterations: The number of sequences to try.
+            max_steps: The maximum length of a sequence.
+
+        Returns:
+            The shrunk failure, or None if every sequence passed.
+
+        Raises:
+            ValueError: If an action's instructions require a signature from
+                a key that is neither the payer nor one of the action's signers.
+        """
+        rng = random.Random(self.seed)
+        snapshot = self.svm.snapshot()
+        try:
+            for _ in range(iterations):
+                steps = [self._draw_step(rng) for _ in range(rng.randint(1, max_steps))]
+                found = self._execute(snapshot, steps)
+                if found is not None:
+                    failing, outcome = found
+                    shrunk, outcome = self._shrink(snapshot, failing, outcome)
+                    return FuzzFailure(
+                        kind=outcome.kind,
+                        steps=shrunk,
+                        original_steps=failing,
+                        result=outcome.result,
+                        exception=outcome.exception,
+                        seed=self.seed,
+                    )
+            return None
+        finally:
+            self.svm.restore(snapshot)
+
+    def _draw_step(self, rng: random.Random) -> Step:
+        action = rng.choice(self.actions)
+        args = {name: strategy.draw(rng) for name, strategy in action.args.items()}
+        return Step(action, args)
+
+    def _execute(
+        self, snapshot: LiteSVMSnapshot, steps: List[Step]
+    ) -> Optional[Tuple[List[Step], _Outcome]]:
+        self.svm.restore(snapshot)
+        for idx, step in enumerate(steps):
+            outcome = self._send(step)
+            if outcome is not None:
+                return steps[: idx + 1], outcome
+        return None
+
+    def _send(self, step: Step) -> Optional[_Outcome]:
+        try:
+            built = step.action.build(**step.args)
+        except BaseException as exc:
+            if not _is_panic(exc):
+                raise
+            return _Outcome(PANIC, exception=exc)
+        ixs = [built] if isinstance(built, Instruction) else list(built)
+        # A fresh blockhash keeps repeated steps from being duplicates.
+        self.svm.expire_blockhash()
+        msg = Message.new_with_blockhash(
+            ixs, self.payer.pubkey(), self.svm.latest_blockhash()
+        )
+        tx = self._sign(step.action, msg)
+        try:
+            result = self.svm.send_transaction(tx)
+        except BaseException as exc:
+            if not _is_panic(exc):
+                raise
+            return _Outcome(PANIC, exception=exc)
+        err = _err(result)
+        if err is not None and not (
+            self.expected_error is not None and self.expected_error(step, err)
+        ):
+            return _Outcome(TRANSACTION_ERROR, result=result)
+        for invariant in self.invariants:
+            try:
+                invariant(self.svm)
+            except BaseException as exc:
+                if not _is_panic(exc):
+                    raise
+                return _Outcome(INVARIANT, result=result, exception=exc)
+        return None
+
+    def _sign(self, action: Action, msg: Message) -> VersionedTransaction:
+        keypairs = {kp.pubkey(): kp for kp in [self.payer, *action.signers]}
+        signers = []
+        for key in msg.account_keys[: msg.header.num_required_signatures]:
+            keypair = keypairs.get(key)
+            if keypair is None:
+                raise ValueError(
+                    f"Action {action.name!r} requires a signature from {key}, "
+                    "which is neither the payer nor one of its signers"
+                )
+            signers.append(keypair)
+        return VersionedTransaction(msg, signers)
+
+    def _shrink(
+        self, snapshot: LiteSVMSnapshot, steps: List[Step], outcome: _Outcome
+    ) -> Tuple[List[Step], _Outcome]:
+        budget = self.max_shrinks
+
+        def attempt(candidate: List[Step]) -> bool:
+            nonlocal budget, steps, outcome
+            if budget <= 0:
+                return False
+            budget -= 1
+            found = self._execute(snapshot, candidate)
+            if found is None or not found[1].matches(outcome):
+                return False
+            steps, outcome = found
+            return True
+
+        improved = True
+        while improved and budget > 0:
+            improved = False
+            chunk = len(steps) // 2
+            while chunk >= 1:
+                start = 0
+                while start + chunk <= len(steps):
+                    if not attempt(steps[:start] + steps[start + chunk :]):
+                        start += 1
+                    else:
+                        improved = True
+                chunk //= 2
+            idx = 0
+            while idx < len(steps):
+                for name, strategy in list(steps[idx].action.args.items()):
+                    # Retry from the simplest candidate after each success.
+                    shrinking = True
+                    while shrinking and idx < len(steps):
+                        shrinking = False
+                        step = steps[idx]
+                        for value in strategy.shrink(step.args[name]):
+                            args = {**step.args, name: value}
+                            candidate = list(steps)
+                            candidate[idx] = Step(step.action, args)
+                            if attempt(candidate):
+                                improved = shrinking = True
+                                break
+                idx += 1
+        self.svm.restore(snapshot)
+        return steps, outcome
+
+
+def _is_panic(exc: BaseException) -> bool:
+    # Rust panics surface as pyo3's PanicException, which isn't an Exception.
+    return isinstance(exc, Exception) or type(exc).__name__ == "PanicException"
+
@@ -0,0 +1,918 @@
+"""A local Solana JSON-RPC server backed by LiteSVM."""
+import base64
+import hashlib
+import json
+import socket
+import struct
+import threading
+from dataclasses import dataclass, field
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from typing import (
+    Any,
+    BinaryIO,
+    Callable,
+    Dict,
+    Iterator,
+    List,
+    Optional,
+    Set,
+    Tuple,
+    Union,
+)
+
+from .account import Account
+from .epoch_info import EpochInfo
+from .litesvm import LiteSVM
+from .pubkey import Pubkey
+from .rpc.config import RpcTransactionLogsFilterMentions
+from .rpc.requests import (
+    AccountSubscribe,
+    AccountUnsubscribe,
+    GetAccountInfo,
+    GetBalance,
+    GetBlockHeight,
+    GetEpochInfo,
+    GetEpochSchedule,
+    GetFeeForMessage,
+    GetGenesisHash,
+    GetHealth,
+    GetLatestBlockhash,
+    GetMinimumBalanceForRentExemption,
+    GetMultipleAccounts,
+    GetProgramAccounts,
+    GetSignatureStatuses,
+    GetSlot,
+    GetTransaction,
+    GetVersion,
+    IsBlockhashValid,
+    LogsSubscribe,
+    LogsUnsubscribe,
+    ProgramSubscribe,
+    ProgramUnsubscribe,
+    RequestAirdrop,
+    SendVersionedTransaction,
+    SignatureSubscribe,
+    SignatureUnsubscribe,
+    SimulateVersionedTransaction,
+    SlotSubscribe,
+    SlotUnsubscribe,
+    request_from_json,
+)
+from .rpc.responses import (
+    AccountNotification,
+    AccountNotificationResult,
+    GetAccountInfoResp,
+    GetBalanceResp,
+    GetBlockHeightResp,
+    GetEpochInfoResp,
+    GetEpochScheduleResp,
+    GetFeeForMessageResp,
+    GetGenesisHashResp,
+    GetHealthResp,
+    GetLatestBlockhashResp,
+    GetMinimumBalanceForRentExemptionResp,
+    GetMultipleAccountsResp,
+    GetProgramAccountsResp,
+    GetProgramAccountsWithContextResp,
+    GetSignatureStatusesResp,
+    GetSlotResp,
+    GetTransactionResp,
+    GetVersionResp,
+    IsBlockhashValidResp,
+    LogsNotification,
+    LogsNotificationResult,
+    Notification,
+    ProgramNotification,
+    ProgramNotificationResult,
+    RequestAirdropResp,
+    RpcBlockhash,
+    RpcKeyedAccount,
+    RpcLogsResponse,
+    RpcResponseContext,
+    RpcSignatureResponse,
+    RpcSimulateTransactionResult,
+    RpcVersionInfo,
+    SendTransactionResp,
+    SignatureNotification,
+    SignatureNotificationResult,
+    SimulateTransactionResp,
+    SlotInfo,
+    SlotNotification,
+    SubscriptionResult,
+    UnsubscribeResult,
+)
+from .signature import Signature
+from .system_program import ID as SYSTEM_PROGRAM_ID
+from .transaction import VersionedTransaction
+from .transaction_metadata import (
+    FailedTransactionMetadata,
+    TransactionMetadata,
+    TransactionResult,
+)
+from .transaction_status import (
+    EncodedConfirmedTransactionWithStatusMeta,
+    EncodedTransactionWithStatusMeta,
+    TransactionErrorType,
+    TransactionReturnData,
+    UiCompiledInstruction,
+    UiInnerInstructions,
+    UiTransactionStatusMeta,
+)
+
+SOLANA_CORE_VERSION = "3.0.4"
+# LiteSVM has no blocks, so the block height is the slot and blockhashes are
+# reported valid for the usual 150 blocks.
+MAX_PROCESSING_AGE = 150
+
+PARSE_ERROR = -32700
+INVALID_REQUEST = -32600
+METHOD_NOT_FOUND = -32601
+INVALID_PARAMS = -32602
+SEND_TRANSACTION_PREFLIGHT_FAILURE = -32002
+
+_WEBSOCKET_GUID = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11"
+_OPCODE_CONTINUATION = 0x0
+_OPCODE_TEXT = 0x1
+_OPCODE_BINARY = 0x2
+_OPCODE_CLOSE = 0x8
+_OPCODE_PING = 0x9
+_OPCODE_PONG = 0xA
+# Payload lengths above 125 are sent as one of these markers, followed by
+# the length as a 16-bit or 64-bit integer.
+_PAYLOAD_LENGTH_16 = 126
+_PAYLOAD_LENGTH_64 = 127
+
+_UNSUBSCRIBE_KINDS = {
+    AccountUnsubscribe: "accountNotification",
+    LogsUnsubscribe: "logsNotification",
+    ProgramUnsubscribe: "programNotification",
+    SignatureUnsubscribe: "signatureNotification",
+    SlotUnsubscribe: "slotNotification",
+}
+
+_B58_ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+
+def _b58encode(data: bytes) -> str:
+    num = int.from_bytes(data, "big")
+    encoded = ""
+    while num:
+        num, rem = divmod(num, 58)
+        encoded = _B58_ALPHABET[rem] + encoded
+    return "1" * (len(data) - len(data.lstrip(b"\0"))) + encoded
+
+
+class RpcError(Exception):
+    """A JSON-RPC error to return to the client."""
+
+    def __init__(self, code: int, message: str, data: Any = None) -> None:
+        """Create a JSON-RPC error.
+
+        Args:
+            code: The JSON-RPC error code.
+            message: The error message.
+            data: Optional JSON-serializable error data.
+        """
+        super().__init__(message)
+        self.code = code
+        self.message = message
+        self.data = data
+
+    def to_dict(self) -> Dict[str, Any]:
+        """The JSON-RPC ``error`` object."""
+        error: Dict[str, Any] = {"code": self.code, "message": self.message}
+        if self.data is not None:
+            error["data"] = self.data
+        return error
+
+
+def _err(result: TransactionResult) -> Optional[TransactionErrorType]:
+    if isinstance(result, FailedTransactionMetadata):
+        return result.err()
+    return None
+
+
+def _meta(result: TransactionResult) -> TransactionMetadata:
+    if isinstance(result, FailedTransactionMetadata):
+        return result.meta()
+    return result
+
+
+@dataclass
+class _LandedTransaction:
+    """A transaction sent since the last notifications."""
+
+    slot: int
+    tx: VersionedTransaction
+    result: TransactionResult
+
+
+@dataclass
+class _SentTransaction:
+    """What ``getTransaction`` needs that the transaction history doesn't keep."""
+
+    tx: VersionedTransaction
+    fee: int
+    pre_balances: List[int]
+    post_balances: List[int]
+
+
+class _WebSocket:
+    """The server side of a websocket connection (RFC 6455)."""
+
+    def __init__(self, sock: socket.socket, rfile: BinaryIO, wfile: BinaryIO) -> None:
+        self._sock = sock
+        self._rfile = rfile
+        self._wfile = wfile
+        self._send_lock = threading.Lock()
+
+    def _read_exact(self, n: int) -> bytes:
+        data = self._rfile.read(n)
+        if len(data) < n:
+            raise ConnectionError("websocket closed")
+        return data
+
+    def _read_frame(self) -> Tuple[bool, int, bytes]:
+        first, second = self._read_exact(2)
+        length = second & 0x7F
+        if length == _PAYLOAD_LENGTH_16:
+            (length,) = struct.unpack("!H", self._read_exact(2))
+        elif length == _PAYLOAD_LENGTH_64:
+            (length,) = struct.unpack("!Q", self._read_exact(8))
+        mask = self._read_exact(4) if second & 0x80 else b""
+        payload = self._read_exact(length)
+        if mask:
+            payload = bytes(b ^ mask[i % 4] for i, b in enumerate(payload))
+        return bool(first & 0x80), first & 0x0F, payload
+
+    def recv(self) -> Optional[str]:
+        """Wait for the next text message. Returns None once the client closes."""
+        message = b""
+        while True:
+            fin, opcode, payload = self._read_frame()
+            if opcode == _OPCODE_CLOSE:
+                self._send_frame(_OPCODE_CLOSE, payload[:2])
+                return None
+            if opcode == _OPCODE_PING:
+                self._send_frame(_OPCODE_PONG, payload)
+            elif opcode in (_OPCODE_TEXT, _OPCODE_BINARY, _OPCODE_CONTINUATION):
+                message += payload
+                if fin:
+                    return message.decode()
+
+    def _send_frame(self, opcode: int, payload: bytes) -> None:
+        length = len(payload)
+        if length < _PAYLOAD_LENGTH_16:
+            header = struct.pack("!BB", 0x80 | opcode, length)
+        elif length < 1 << 16:
+            header = struct.pack("!BBH", 0x80 | opcode, _PAYLOAD_LENGTH_16, length)
+        else:
+            header = struct.pack("!BBQ", 0x80 | opcode, _PAYLOAD_LENGTH_64, length)
+        with self._send_lock:
+            self._wfile.write(header + payload)
+            self._wfile.flush()
+
+    def send(self, message: str) -> None:
+        """Send a text message."""
+        self._send_frame(_OPCODE_TEXT, message.encode())
+
+    def close(self) -> None:
+        """Send a close frame and shut the socket down."""
+        try:
+            self._send_frame(_OPCODE_CLOSE, b"")
+            self._sock.shutdown(socket.SHUT_RDWR)
+        except OSError:
+            pass
+
+
+@dataclass
+class _Subscription:
+    kind: str
+    connection: _WebSocket
+    request: Any
+    # The last state sent for account and program subscriptions.
+    seen: Dict[Pubkey, Account] = field(default_factory=dict)
+
+
+def _ui_inner_instructions(meta: TransactionMetadata) -> List[UiInnerInstructions]:
+    return [
+        UiInnerInstructions(
+            index,
+            [
+                UiCompiledInstruction(
+                    ix.instruction.program_id_index,
+                    bytes(ix.instruction.accounts),
+                    _b58encode(ix.instruction.data),
+                    ix.stack_height,
+                )
+                for ix in ixs
+            ],
+        )
+        for index, ixs in enumerate(meta.inner_instructions())
+        if ixs
+    ]
+
+
+def _return_data(meta: TransactionMetadata) -> Optional[TransactionReturnData]:
+    return_data = meta.return_data()
+    return return_data if return_data.data else None
+
+
+def _simulation_result(
+    err: Optional[TransactionErrorType],
+    meta: TransactionMetadata,
+    accounts: Optional[List[Optional[Account]]] = None,
+    fee: Optional[int] = None,
+) -> RpcSimulateTransactionResult:
+    return RpcSimulateTransactionResult(
+        err=err,
+        logs=meta.logs(),
+        accounts=accounts,
+        units_consumed=meta.compute_units_consumed(),
+        return_data=_return_data(meta),
+        inner_instructions=_ui_inner_instructions(meta),
+        fee=fee,
+    )
+
+
+def _fee(meta: TransactionMetadata) -> Optional[int]:
+    report = meta.fee_report()
+    return None if report is None else report.total()
+
+
+def _charged_fee(meta: TransactionMetadata) -> int:
+    report = meta.fee_report()
+    if report is None:
+        return 0
+    return report.signature_fee + report.precompile_signature_fee
+
+
+class LiteSVMServer:
+    """Serves the Solana JSON-RPC API over HTTP, answering from a LiteSVM instance.
+
+    Requests are parsed into the :mod:`solders.rpc.requests` types and answered
+    with the :mod:`solders.rpc.responses` types, so any Solana client can use
+    it in place of ``solana-test-validator``. Batch requests are supported.
+    Requests are handled one at a time, so the ``LiteSVM`` instance must not
+    be used from other threads while the server is running.
+
+    Transactions must be sent base64-encoded, and account data and
+    transactions are always returned base64-encoded. ``getFeeForMessage`` and
+    ``simulateTransaction`` report the fee a cluster would charge, including
+    the priority fee, while ``getTransaction`` reports the fee LiteSVM
+    charged, which leaves the priority fee out. Transactions are looked up in
+    the ``LiteSVM`` transaction history, so they are forgotten once it evicts
+    them.
+
+    ``getGenesisHash`` returns the instance's latest blockhash at the time the
+    server was created. LiteSVM doesn't track block height, so
+    ``getBlockHeight`` and ``getEpochInfo`` report the slot instead, as if no
+    slot had been skipped.
+
+    The same port accepts websocket connections for the ``accountSubscribe``,
+    ``programSubscribe``, ``logsSubscribe``, ``signatureSubscribe`` and
+    ``slotSubscribe`` methods. Notifications are sent after each request the
+    server handles. Call :meth:`notify` after changing the ``LiteSVM``
+    instance directly, for example with ``warp_to_slot``.
+
+    Example:
+        >>> from urllib.request import urlopen
+        >>> from solders.litesvm import LiteSVM
+        >>> from solders.litesvm_server import LiteSVMServer
+        >>> with LiteSVMServer(LiteSVM()) as server:
+        ...     body = b'{"jsonrpc":"2.0","id":1,"method":"getHealth"}'
+        ...     urlopen(server.url, body).read()
+        b'{"jsonrpc":"2.0","result":"ok","id":1}'
+    """
+
+    def __init__(self, svm: LiteSVM, host: str = "127.0.0.1", port: int = 0) -> None:
+        """Bind the server. Call :meth:`start` or use it as a context manager to serve.
+
+        Args:
+            svm: The LiteSVM instance to serve.
+            host: The interface to bind to.
+            port: The port to bind to. Pass 0 to pick a free port.
+        """
+        self.svm = svm
+        self._genesis_hash = svm.latest_blockhash()
+        self._lock = threading.Lock()
+        self._sent: Dict[Signature, _SentTransaction] = {}
+        self._transaction_count = 0
+        self._handlers: Dict[type, Callable[[Any], Any]] = {
+            GetAccountInfo: self._get_account_info,
+            GetBalance: self._get_balance,
+            GetBlockHeight: self._get_block_height,
+            GetEpochInfo: self._get_epoch_info,
+            GetEpochSchedule: self._get_epoch_schedule,
+            GetFeeForMessage: self._get_fee_for_message,
+            GetGenesisHash: self._get_genesis_hash,
+            GetHealth: self._get_health,
+            GetLatestBlockhash: self._get_latest_blockhash,
+            GetMinimumBalanceForRentExemption: self._get_minimum_balance,
+            GetMultipleAccounts: self._get_multiple_accounts,
+            GetProgramAccounts: self._get_program_accounts,
+            GetSignatureStatuses: self._get_signature_statuses,
+            GetSlot: self._get_slot,
+            GetTransaction: self._get_transaction,
+            GetVersion: self._get_version,
+            IsBlockhashValid: self._is_blockhash_valid,
+            RequestAirdrop: self._request_airdrop,
+            SendVersionedTransaction: self._send_transaction,
+            SimulateVersionedTransaction: self._simulate_transaction,
+        }
+        self._pubsub_handlers: Dict[type, Callable[[Any, _WebSocket], Any]] = {
+            AccountSubscribe: self._account_subscribe,
+            LogsSubscribe: self._logs_subscribe,
+            ProgramSubscribe: self._program_subscribe,
+            SignatureSubscribe: self._signature_subscribe,
+            SlotSubscribe: self._slot_subscribe,
+            **{kind: self._unsubscribe for kind in _UNSUBSCRIBE_KINDS},
+        }
+        self._connections: Set[_WebSocket] = set()
+        self._subscriptions: Dict[int, _Subscription] = {}
+        self._next_subscription_id = 0
+        self._unpublished: List[_LandedTransaction] = []
+        self._last_slot = svm.get_clock().slot
+        self._httpd = ThreadingHTTPServer((host, port), _make_handler(self))
+        self._thread: Optional[threading.Thread] = None
+
+    @property
+    def url(self) -> str:
+        """The HTTP URL to point clients at."""
+        host, port = self._httpd.server_address[:2]
+        return f"http://{host}:{port}"
+
+    @property
+    def ws_url(self) -> str:
+        """The websocket URL to point clients at."""
+        host, port = self._httpd.server_address[:2]
+        return f"ws://{host}:{port}"
+
+    def start(self) -> "LiteSVMServer":
+        """Start serving in a background thread.
+
+        Returns:
+            The server itself.
+        """
+        if self._thread is None:
+            self._thread = threading.Thread(
+                target=self._httpd.serve_forever, daemon=True
+            )
+            self._thread.start()
+        return self
+
+    def stop(self) -> None:
+        """Stop serving and release the port."""
+        if self._thread is not None:
+            self._httpd.shutdown()
+            self._thread.join()
+            self._thread = None
+        for connection in list(self._connections):
+            connection.close()
+        self._httpd.server_close()
+
+    def __enter__(self) -> "LiteSVMServer":
+        """Start serving."""
+        return self.start()
+
+    def __exit__(self, *_args: Any) -> None:
+        """Stop serving."""
+        self.stop()
+
+    def handle(self, raw: Union[str, bytes]) -> Optional[str]:
+        """Answer a raw JSON-RPC request or batch without going through HTTP.
+
+        Args:
+            raw: The request body.
+
+        Returns:
+            The response body, or None if there is nothing to respond
+            (an empty batch).
+        """
+        response = self._respond(raw)
+        self.notify()
+        return response
+
+    def notify(self) -> None:
+        """Send the websocket notifications for changes since the last call.
+
+        This runs after every request the server handles, so it is only needed
+        after changing the ``LiteSVM`` instance directly.
+        """
+        with self._lock:
+            context = self._context()
+            landed, self._unpublished = self._unpublished, []
+            for sub_id, sub in list(self._subscriptions.items()):
+                for notification in self._notifications(sub_id, sub, context, landed):
+                    message = {
+                        "jsonrpc": "2.0",
+                        "method": sub.kind,
+                        "params": json.loads(notification.to_json()),
+                    }
+                    try:
+                        sub.connection.send(_dumps(message))
+                    except OSError:
+                        self._drop_connection(sub.connection)
+            self._last_slot = context.slot
+
+    def _respond(
+        self, raw: Union[str, bytes], connection: Optional[_WebSocket] = None
+    ) -> Optional[str]:
+        try:
+            parsed = json.loads(raw)
+        except ValueError as e:
+            return _dumps(_error_response(None, RpcError(PARSE_ERROR, str(e))))
+        if isinstance(parsed, list):
+            responses = [self._handle_one(req, connection) for req in parsed]
+            return _dumps(responses) if responses else None
+        return _dumps(self._handle_one(parsed, connection))
+
+    def _serve_websocket(self, connection: _WebSocket) -> None:
+        self._connections.add(connection)
+        try:
+            while True:
+                raw = connection.recv()
+                if raw is None:
+                    break
+                response = self._respond(raw, connection)
+                if response is not None:
+                    connection.send(response)
+                self.notify()
+        except (OSError, ValueError):
+            pass
+        finally:
+            with self._lock:
+                self._drop_connection(connection)
+            connection.close()
+
+    def _drop_connection(self, connection: _WebSocket) -> None:
+        self._connections.discard(connection)
+        for sub_id, sub in list(self._subscriptions.items()):
+            if sub.connection is connection:
+                del self._subscriptions[sub_id]
+
+    def _handle_one(
+        self, req: Any, connection: Optional[_WebSocket] = None
+    ) -> Dict[str, Any]:
+        if not isinstance(req, dict):
+            return _error_response(None, RpcError(INVALID_REQUEST, "Invalid request"))
+        # Request ids may be strings, but the typed requests only hold integers.
+        req_id = req.get("id")
+        method = req.get("method")
+        method_not_found = RpcError(METHOD_NOT_FOUND, f"Method not found: {method}")
+        normalized = {**req, "id": 0}
+        if normalized.get("params") == []:
+            del normalized["params"]
+        try:
+            body = request_from_json(json.dumps(normalized))
+        except ValueError as e:
+            if "unknown variant" in str(e):
+                return _error_response(req_id, method_not_found)
+            error = RpcError(INVALID_PARAMS, f"Invalid params: {e}")
+            return _error_response(req_id, error)
+        # Like a validator's websocket port, websockets only serve subscriptions.
+        handlers = self._handlers if connection is None else self._pubsub_handlers
+        handler: Optional[Callable[..., Any]] = handlers.get(type(body))
+        if handler is None:
+            return _error_response(req_id, method_not_found)
+        args = (body,) if connection is None else (body, connection)
+        try:
+            with self._lock:
+                resp = handler(*args)
+        except RpcError as e:
+            return _error_response(req_id, e)
+        return {**json.loads(resp.to_json()), "id": req_id}
+
+    def _context(self) -> RpcResponseContext:
+        return RpcResponseContext(self.svm.get_clock().slot)
+
+    def _blockhash(self) -> RpcBlockhash:
+        slot = self.svm.get_clock().slot
+        return RpcBlockhash(self.svm.latest_blockhash(), slot + MAX_PROCESSING_AGE)
+
+    def _get_account_info(self, req: GetAccountInfo) -> GetAccountInfoResp:
+        return GetAccountInfoResp(self.svm.get_account(req.pubkey), self._context())
+
+    def _get_balance(self, req: GetBalance) -> GetBalanceResp:
+        return GetBalanceResp(self.svm.get_balance(req.pubkey) or 0, self._context())
+
+    def _get_block_height(self, _req: GetBlockHeight) -> GetBlockHeightResp:
+        return GetBlockHeightResp(self.svm.get_clock().slot)
+
+    def _get_epoch_info(self, _req: GetEpochInfo) -> GetEpochInfoResp:
+        clock = self.svm.get_clock()
+        schedule = self.svm.get_epoch_schedule()
+        first_slot = schedule.get_first_slot_in_epoch(clock.epoch)
+        info = EpochInfo(
+            epoch=clock.epoch,
+            slot_index=clock.slot - first_slot,
+            slots_in_epoch=schedule.get_slots_in_epoch(clock.epoch),
+            absolute_slot=clock.slot,
+            block_height=clock.slot,
+            transaction_count=self._transaction_count,
+        )
+        return GetEpochInfoResp(info)
+
+    def _get_epoch_schedule(self, _req: GetEpochSchedule) -> GetEpochScheduleResp:
+        return GetEpochScheduleResp(self.svm.get_epoch_schedule())
+
+    def _get_fee_for_message(self, req: GetFeeForMessage) -> GetFeeForMessageResp:
+        signatures = [Signature.default()] * req.message.header.num_required_signatures
+        tx = VersionedTransaction.populate(req.message, signatures)
+        meta = _meta(self.svm.simulate_transaction(tx))
+        return GetFeeForMessageResp(_fee(meta), self._context())
+
+    def _get_genesis_hash(self, _req: GetGenesisHash) -> GetGenesisHashResp:
+        return GetGenesisHashResp(self._genesis_hash)
+
+    def _get_health(self, _req: GetHealth) -> GetHealthResp:
+        return GetHealthResp("ok")
+
+    def _get_latest_blockhash(self, _req: GetLatestBlockhash) -> GetLatestBlockhashResp:
+        return GetLatestBlockhashResp(self._blockhash(), self._context())
+
+    def _get_minimum_balance(
+        self, req: GetMinimumBalanceForRentExemption
+    ) -> GetMinimumBalanceForRentExemptionResp:
+        balance = self.svm.minimum_balance_for_rent_exemption(req.length)
+        return GetMinimumBalanceForRentExemptionResp(balance)
+
+    def _get_multiple_accounts(
+        self, req: GetMultipleAccounts
+    ) -> GetMultipleAccountsResp:
+        accounts = [self.svm.get_account(pubkey) for pubkey in req.accounts]
+        return GetMultipleAccountsResp(accounts, self._context())
+
+    def _get_program_accounts(
+        self, req: GetProgramAccounts
+    ) -> Union[GetProgramAccountsResp, GetProgramAccountsWithContextResp]:
+        filters = req.config.filters if req.config is not None else None
+        keyed = [
+            RpcKeyedAccount(pubkey, account)
+            for pubkey, account in self.svm.get_program_accounts(req.program, filters)
+        ]
+        if req.config is not None and req.config.with_context:
+            return GetProgramAccountsWithContextResp(keyed, self._context())
+        return GetProgramAccountsResp(keyed)
+
+    def _get_signature_statuses(
+        self, req: GetSignatureStatuses
+    ) -> GetSignatureStatusesResp:
+        statuses = self.svm.get_signature_statuses(req.signatures)
+        return GetSignatureStatusesResp(statuses, self._context())
+
+    def _get_slot(self, _req: GetSlot) -> GetSlotResp:
+        return GetSlotResp(self.svm.get_clock().slot)
+
+    def _get_transaction(self, req: GetTransaction) -> GetTransactionResp:
+        sent = self._sent.get(req.signature)
+        result = self.svm.get_transaction(req.signature)
+        if sent is None or result is None:
+            return GetTransactionResp(None)
+        meta = _meta(result)
+        (status,) = self.svm.get_signature_statuses([req.signature])
+        # Only the signature listing has the block time. It is None for
+        # transactions older than the payer's newest 1000.
+        block_time = next(
+            (
+                info.block_time
+                for info in self.svm.get_signatures_for_address(
+                    sent.tx.message.account_keys[0]
+                )
+                if info.signature == req.signature
+            ),
+            None,
+        )
+        status_meta = UiTransactionStatusMeta(
+            err=_err(result),
+            fee=sent.fee,
+            pre_balances=sent.pre_balances,
+            post_balances=sent.post_balances,
+            inner_instructions=_ui_inner_instructions(meta),
+            log_messages=meta.logs(),
+            pre_token_balances=[],
+            post_token_balances=[],
+            rewards=[],
+            return_data=_return_data(meta),
+            compute_units_consumed=meta.compute_units_consumed(),
+        )
+        tx = EncodedTransactionWithStatusMeta(sent.tx, status_meta, sent.tx.version())
+        slot = status.slot if status is not None else self.svm.get_clock().slot
+        return GetTransactionResp(
+            EncodedConfirmedTransactionWithStatusMeta(slot, tx, block_time)
+        )
+
+    def _get_version(self, _req: GetVersion) -> GetVersionResp:
+        return GetVersionResp(RpcVersionInfo(SOLANA_CORE_VERSION))
+
+    def _is_blockhash_valid(self, req: IsBlockhashValid) -> IsBlockhashValidResp:
+        return IsBlockhashValidResp(req.blockhash == self.svm.latest_blockhash())
+
+    def _request_airdrop(self, req: RequestAirdrop) -> RequestAirdropResp:
+        result = self.svm.airdrop(req.pubkey, req.lamports)
+        if isinstance(result, FailedTransactionMetadata):
+            raise RpcError(INVALID_PARAMS, f"Airdrop failed: {result.err()}")
+        return RequestAirdropResp(result.signature())
+
+    def _send_transaction(self, req: SendVersionedTransaction) -> SendTransactionResp:
+        tx = req.tx
+        if req.config is None or not req.config.skip_preflight:
+            preflight = self.svm.simulate_transaction(tx)
+            if isinstance(preflight, FailedTransactionMetadata):
+                err = preflight.err()
+                raise RpcError(
+                    SEND_TRANSACTION_PREFLIGHT_FAILURE,
+                    f"Transaction simulation failed: {err}",
+                    json.loads(_simulation_result(err, preflight.meta()).to_json()),
+                )
+        keys = tx.message.account_keys
+        slot = self.svm.get_clock().slot
+        pre_balances = [self.svm.get_balance(key) or 0 for key in keys]
+        result = self.svm.send_transaction(tx)
+        signature = tx.signatures[0]
+        self._sent[signature] = _SentTransaction(
+            tx=tx,
+            fee=_charged_fee(_meta(result)),
+            pre_balances=pre_balances,
+            post_balances=[self.svm.get_balance(key) or 0 for key in keys],
+        )
+        # Forget the transactions the capped transaction history has evicted.
+        while self._sent:
+            oldest = next(iter(self._sent))
+            if self.svm.get_transaction(oldest) is not None:
+                break
+            del self._sent[oldest]
+        self._transaction_count += 1
+        self._unpublished.append(_LandedTransaction(slot, tx, result))
+        return SendTransactionResp(signature)
+
+    def _simulate_transaction(
+        self, req: SimulateVersionedTransaction
+    ) -> SimulateTransactionResp:
+        result = self.svm.simulate_transaction(req.tx)
+        if isinstance(result, FailedTransactionMetadata):
+            meta = result.meta()
+            sim = _simulation_result(result.err(), meta, fee=_fee(meta))
+        else:
+            accounts = None
+            accounts_config = req.config.accounts if req.config is not None else None
+            if accounts_config is not None:
+                post: Dict[Pubkey, Account] = dict(result.post_accounts())
+                accounts = [
+                    post.get(address, self.svm.get_account(address))
+                    for address in accounts_config.addresses
+                ]
+            meta = result.meta()
+            sim = _simulation_result(None, meta, accounts, _fee(meta))
+        return SimulateTransactionResp(sim, self._context())
+
+    def _subscribe(
+        self,
+        kind: str,
+        req: Any,
+        connection: _WebSocket,
+        seen: Optional[Dict[Pubkey, Account]] = None,
+    ) -> SubscriptionResult:
+        sub_id = self._next_subscription_id
+        self._next_subscription_id += 1
+        self._subscriptions[sub_id] = _Subscription(kind, connection, req, seen or {})
+        return SubscriptionResult(0, sub_id)
+
+    def _account_subscribe(
+        self, req: AccountSubscribe, connection: _WebSocket
+    ) -> SubscriptionResult:
+        account = self.svm.get_account(req.account)
+        seen = {} if account is None else {req.account: account}
+        return self._subscribe("accountNotification", req, connection, seen)
+
+    def _logs_subscribe(
+        self, req: LogsSubscribe, connection: _WebSocket
+    ) -> SubscriptionResult:
+        return self._subscribe("logsNotification", req, connection)
+
+    def _program_subscribe(
+        self, req: ProgramSubscribe, connection: _WebSocket
+    ) -> SubscriptionResult:
+        seen = self._program_accounts(req)
+        return self._subscribe("programNotification", req, connection, seen)
+
+    def _signature_subscribe(
+        self, req: SignatureSubscribe, connection: _WebSocket
+    ) -> SubscriptionResult:
+        return self._subscribe("signatureNotification", req, connection)
+
+    def _slot_subscribe(
+        self, req: SlotSubscribe, connection: _WebSocket
+    ) -> SubscriptionResult:
+        return self._subscribe("slotNotification", req, connection)
+
+    def _unsubscribe(self, req: Any, connection: _WebSocket) -> UnsubscribeResult:
+        sub = self._subscriptions.get(req.subscription_id)
+        if (
+            sub is None
+            or sub.connection is not connection
+            or sub.kind != _UNSUBSCRIBE_KINDS[type(req)]
+        ):
+            raise RpcError(INVALID_PARAMS, "Invalid subscription id.")
+        del self._subscriptions[req.subscription_id]
+        return UnsubscribeResult(0, True)
+
+    def _program_accounts(self, req: ProgramSubscribe) -> Dict[Pubkey, Account]:
+        filters = req.config.filters if req.config is not None else None
+        return dict(self.svm.get_program_accounts(req.program, filters))
+
+    def _notifications(
+        self,
+        sub_id: int,
+        sub: _Subscription,
+        context: RpcResponseContext,
+        landed: List[_LandedTransaction],
+    ) -> Iterator[Notification]:
+        req = sub.request
+        if isinstance(req, SlotSubscribe):
+            if context.slot != self._last_slot:
+                info = SlotInfo(context.slot, self._last_slot, context.slot)
+                yield SlotNotification(info, sub_id)
+        elif isinstance(req, AccountSubscribe):
+            account = self.svm.get_account(req.account)
+            if account is None and req.account in sub.seen:
+                # Closed accounts are reported as empty system accounts.
+                account = Account(0, b"", SYSTEM_PROGRAM_ID)
+            if account is not None and sub.seen.get(req.account) != account:
+                sub.seen = {req.account: account}
+                result = AccountNotificationResult(account, context)
+                yield AccountNotification(result, sub_id)
+        elif isinstance(req, ProgramSubscribe):
+            accounts = self._program_accounts(req)
+            for pubkey, account in accounts.items():
+                if sub.seen.get(pubkey) != account:
+                    keyed = RpcKeyedAccount(pubkey, account)
+                    result = ProgramNotificationResult(keyed, context)
+                    yield ProgramNotification(result, sub_id)
+            sub.seen = accounts
+        elif isinstance(req, LogsSubscribe):
+            for tx in landed:
+                mentions = req.filter_
+                if isinstance(mentions, RpcTransactionLogsFilterMentions) and (
+                    mentions.pubkey not in tx.tx.message.account_keys
+                ):
+                    continue
+                logs = RpcLogsResponse(
+                    tx.tx.signatures[0], _err(tx.result), _meta(tx.result).logs()
+                )
+                result = LogsNotificationResult(logs, RpcResponseContext(tx.slot))
+                yield LogsNotification(result, sub_id)
+        elif isinstance(req, SignatureSubscribe):
+            (status,) = self.svm.get_signature_statuses([req.signature])
+            if status is not None:
+                # Signature subscriptions end after their first notification.
+                del self._subscriptions[sub_id]
+                response = RpcSignatureResponse(status.err)
+                result = SignatureNotificationResult(
+                    response, RpcResponseContext(status.slot)
+                )
+                yield SignatureNotification(result, sub_id)
+
+
+def _dumps(obj: Any) -> str:
+    return json.dumps(obj, separators=(",", ":"))
+
+
+def _error_response(req_id: Any, error: RpcError) -> Dict[str, Any]:
+    return {"jsonrpc": "2.0", "error": error.to_dict(), "id": req_id}
+
+
+def _make_handler(server: LiteSVMServer) -> type:
+    class Handler(BaseHTTPRequestHandler):
+        def do_GET(self) -> None:
+            key = self.headers.get("Sec-WebSocket-Key")
+            if self.headers.get("Upgrade", "").lower() != "websocket" or not key:
+                self.send_error(405)
+                return
+            digest = hashlib.sha1((key + _WEBSOCKET_GUID).encode()).digest()
+            self.send_response(101)
+            self.send_header("Upgrade", "websocket")
+            self.send_header("Connection", "Upgrade")
+            self.send_header("Sec-WebSocket-Accept", base64.b64encode(digest).decode())
+            self.end_headers()
+            self.wfile.flush()
+            self.close_connection = True
+            server._serve_websocket(_WebSocket(self.connection, self.rfile, self.wfile))
+
+        def do_POST(self) -> None:
+            length = int(self.headers.get("Content-Length", 0))
+            response = server.handle(self.rfile.read(length))
+            body = (response or "").encode()
+            self.send_response(200)
+            self.send_header("Content-Type", "application/json")
+            self.send_header("Content-Length", str(len(body)))
+            self.end_headers()
+            self.wfile.write(body)
+
+        def log_message(self, *_args: Any) -> None:
+            pass
+
+    return Handler
+
+
+__all__ = ["LiteSVMServer", "RpcError"]
@@ -1,4 +1,4 @@
-from typing import Sequence, Union
+from typing import List, Sequence, Union
 
 from ..solders import (
     AccountSubscribe,
@@ -76,9 +76,15 @@ from ..solders import (
     VoteSubscribe,
     VoteUnsubscribe,
 )
+from ..solders import (
+    batch_requests_from_json as _batch_from_json,
+)
 from ..solders import (
     batch_requests_to_json as _batch_to_json,
 )
+from ..solders import (
+    request_from_json as _request_from_json,
+)
 
 Body = Union[
     GetAccountInfo,
@@ -175,6 +181,43 @@ def batch_to_json(reqs: Sequence[Body]) -> str:
     return _batch_to_json(reqs)
 
 
+def request_from_json(raw: str) -> Body:
+    """Parse a single JSON-RPC request into the matching request object.
+
+    Transactions sent with ``sendTransaction`` or ``simulateTransaction``
+    are parsed as :class:`~solders.transaction.VersionedTransaction`.
+
+    Args:
+        raw: The request JSON.
+
+    Returns:
+        Body: The parsed request.
+
+    Example:
+        >>> from solders.rpc.requests import request_from_json
+        >>> request_from_json('{"method":"getEpochSchedule","jsonrpc":"2.0","id":1}')
+        GetEpochSchedule {
+            base: RequestBase {
+                jsonrpc: TwoPointOh,
+                id: 1,
+            },
+        }
+    """
+    return _request_from_json(raw)
+
+
+def batch_from_json(raw: str) -> List[Body]:
+    """Parse a batch request JSON into a list of request objects.
+
+    Args:
+        raw: The batch request JSON.
+
+    Returns:
+        list[Body]: The parsed requests.
+    """
+    return _batch_from_json(raw)
+
+
 __all__ = [
     "Body",
     "GetAccountInfo",
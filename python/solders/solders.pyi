@@ -1,6 +1,7 @@
 from pathlib import Path
 from typing import (
     Any,
+    Callable,
     ClassVar,
     Dict,
     Final,
@@ -1532,6 +1533,53 @@ class TransactionReturnData:
     @staticmethod
     def from_json(raw: str) -> "TransactionReturnData": ...
 
+class ProgramInvocation:
+    @property
+    def program_id(self) -> Pubkey: ...
+    @property
+    def depth(self) -> int: ...
+    @property
+    def logs(self) -> List[str]: ...
+    @property
+    def data(self) -> List[List[bytes]]: ...
+    @property
+    def consumed(self) -> Optional[int]: ...
+    @property
+    def budget(self) -> Optional[int]: ...
+    @property
+    def return_data(self) -> Optional[TransactionReturnData]: ...
+    @property
+    def success(self) -> Optional[bool]: ...
+    @property
+    def error(self) -> Optional[str]: ...
+    @property
+    def children(self) -> List["ProgramInvocation"]: ...
+    @staticmethod
+    def from_bytes(raw_bytes: bytes) -> "ProgramInvocation": ...
+    @staticmethod
+    def from_json(raw: str) -> "ProgramInvocation": ...
+    def to_json(self) -> str: ...
+    def __bytes__(self) -> bytes: ...
+    def __str__(self) -> str: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self, other: "ProgramInvocation", op: int) -> bool: ...
+
+class ProgramLogs:
+    def __init__(self, logs: Sequence[str]) -> None: ...
+    @property
+    def invocations(self) -> List[ProgramInvocation]: ...
+    @property
+    def truncated(self) -> bool: ...
+    @staticmethod
+    def from_bytes(raw_bytes: bytes) -> "ProgramLogs": ...
+    @staticmethod
+    def from_json(raw: str) -> "ProgramLogs": ...
+    def to_json(self) -> str: ...
+    def __bytes__(self) -> bytes: ...
+    def __str__(self) -> str: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self, other: "ProgramLogs", op: int) -> bool: ...
+
 class UiTransactionStatusMeta:
     def __init__(
         self,
@@ -1561,6 +1609,7 @@ class UiTransactionStatusMeta:
     def inner_instructions(self) -> Optional[List[UiInnerInstructions]]: ...
     @property
     def log_messages(self) -> Optional[List[str]]: ...
+    def parsed_logs(self) -> Optional[ProgramLogs]: ...
     @property
     def pre_token_balances(self) -> Optional[List[UiTransactionTokenBalance]]: ...
     @property
@@ -1973,12 +2022,124 @@ class InnerInstruction:
     def __repr__(self) -> str: ...
     def __richcmp__(self, other: "InnerInstruction", op: int) -> bool: ...
 
+class AccountDiff:
+    @property
+    def pubkey(self) -> Pubkey: ...
+    @property
+    def pre_lamports(self) -> int: ...
+    @property
+    def post_lamports(self) -> int: ...
+    @property
+    def pre_owner(self) -> Pubkey: ...
+    @property
+    def post_owner(self) -> Pubkey: ...
+    @property
+    def pre_data_len(self) -> int: ...
+    @property
+    def post_data_len(self) -> int: ...
+    @property
+    def changed_data(self) -> List[Tuple[int, int]]: ...
+    @property
+    def pre_token_amount(self) -> Optional[int]: ...
+    @property
+    def post_token_amount(self) -> Optional[int]: ...
+    def lamports_delta(self) -> int: ...
+    def token_amount_delta(self) -> Optional[int]: ...
+    def is_changed(self) -> bool: ...
+    @staticmethod
+    def from_bytes(raw_bytes: bytes) -> "AccountDiff": ...
+    @staticmethod
+    def from_json(raw: str) -> "AccountDiff": ...
+    def to_json(self) -> str: ...
+    def __bytes__(self) -> bytes: ...
+    def __str__(self) -> str: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self, other: "AccountDiff", op: int) -> bool: ...
+
+class FeeReport:
+    @property
+    def payer(self) -> Pubkey: ...
+    @property
+    def lamports_per_signature(self) -> int: ...
+    @property
+    def signature_fee(self) -> int: ...
+    @property
+    def precompile_signature_fee(self) -> int: ...
+    @property
+    def compute_unit_limit(self) -> int: ...
+    @property
+    def compute_unit_price(self) -> int: ...
+    @property
+    def priority_fee(self) -> int: ...
+    @property
+    def payer_balance(self) -> int: ...
+    @property
+    def payer_rent_exempt_minimum(self) -> int: ...
+    def total(self) -> int: ...
+    def payer_below_rent_exempt_minimum(self) -> bool: ...
+    @staticmethod
+    def from_bytes(raw_bytes: bytes) -> "FeeReport": ...
+    @staticmethod
+    def from_json(raw: str) -> "FeeReport": ...
+    def to_json(self) -> str: ...
+    def __bytes__(self) -> bytes: ...
+    def __str__(self) -> str: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self, other: "FeeReport", op: int) -> bool: ...
+
+class InstructionComputeUnits:
+    @property
+    def program_id(self) -> Pubkey: ...
+    @property
+    def stack_height(self) -> int: ...
+    @property
+    def consumed(self) -> Optional[int]: ...
+    @property
+    def budget(self) -> Optional[int]: ...
+    @property
+    def instruction(self) -> Optional[CompiledInstruction]: ...
+    @property
+    def children(self) -> List["InstructionComputeUnits"]: ...
+    def exclusive_consumed(self) -> Optional[int]: ...
+    @staticmethod
+    def from_bytes(raw_bytes: bytes) -> "InstructionComputeUnits": ...
+    @staticmethod
+    def from_json(raw: str) -> "InstructionComputeUnits": ...
+    def to_json(self) -> str: ...
+    def __bytes__(self) -> bytes: ...
+    def __str__(self) -> str: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self, other: "InstructionComputeUnits", op: int) -> bool: ...
+
+class ProgramTrace:
+    @property
+    def program_id(self) -> Pubkey: ...
+    @property
+    def stack_height(self) -> int: ...
+    def pcs(self) -> List[int]: ...
+    def registers(self) -> List[List[int]]: ...
+    def __len__(self) -> int: ...
+    @staticmethod
+    def from_bytes(raw_bytes: bytes) -> "ProgramTrace": ...
+    @staticmethod
+    def from_json(raw: str) -> "ProgramTrace": ...
+    def to_json(self) -> str: ...
+    def __bytes__(self) -> bytes: ...
+    def __str__(self) -> str: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self, other: "ProgramTrace", op: int) -> bool: ...
+
 class TransactionMetadata:
     def signature(self) -> Signature: ...
     def logs(self) -> List[str]: ...
     def inner_instructions(self) -> List[List[InnerInstruction]]: ...
     def compute_units_consumed(self) -> int: ...
     def transaction_return_data(self) -> TransactionReturnData: ...
+    def compute_units_profile(self) -> List[InstructionComputeUnits]: ...
+    def parsed_logs(self) -> ProgramLogs: ...
+    def account_diffs(self) -> Optional[List[AccountDiff]]: ...
+    def fee_report(self) -> Optional[FeeReport]: ...
+    def traces(self) -> Optional[List[ProgramTrace]]: ...
     @staticmethod
     def from_bytes(raw_bytes: bytes) -> "TransactionMetadata": ...
     @staticmethod
@@ -6968,6 +7129,7 @@ Body = Union[
 
 def batch_requests_to_json(reqs: Sequence[Body]) -> str: ...
 def batch_requests_from_json(raw: str) -> List[Body]: ...
+def request_from_json(raw: str) -> Body: ...
 
 ACCOUNT_STORAGE_OVERHEAD: Final[int]
 DEFAULT_BURN_PERCENT: Final[int]
@@ -7026,11 +7188,31 @@ class FeatureSet:
     def __repr__(self) -> str: ...
     def __richcmp__(self, other: "FeatureSet", op: int) -> bool: ...
 
+class LiteSVMSnapshot: ...
+
+class AccountInfo:
+    @property
+    def key(self) -> Pubkey: ...
+    @property
+    def is_signer(self) -> bool: ...
+    @property
+    def is_writable(self) -> bool: ...
+    lamports: int
+    data: bytes
+    owner: Pubkey
+    def __repr__(self) -> str: ...
+
 class LiteSVM:
-    def __init__(self) -> None: ...
+    def __init__(
+        self, feature_set: Optional[FeatureSet] = None, seed: Optional[int] = None
+    ) -> None: ...
     @staticmethod
     def default() -> "LiteSVM": ...
+    def save(self, path: Path) -> None: ...
+    @staticmethod
+    def load(path: Path) -> "LiteSVM": ...
     def set_compute_budget(self, budget: ComputeBudget) -> None: ...
+    def set_feature_set(self, feature_set: FeatureSet) -> None: ...
     def set_sigverify(self, sigverify: bool) -> None: ...
     def set_blockhash_check(self, check: bool) -> None: ...
     def set_sysvars(self) -> None: ...
@@ -7039,25 +7221,123 @@ class LiteSVM:
     def set_default_programs(self) -> None: ...
     def set_transaction_history(self, capacity: int) -> None: ...
     def set_log_bytes_limit(self, limit: Optional[int]) -> None: ...
+    def set_auto_advance(self, enabled: bool, slot_duration_ms: int = 400) -> None: ...
+    def set_account_diffs(self, enabled: bool) -> None: ...
+    def set_tracing(self, enabled: bool) -> None: ...
+    def coverage(self) -> Dict[Pubkey, Dict[int, int]]: ...
+    def coverage_report(self) -> str: ...
+    def reset_coverage(self) -> None: ...
     def minimum_balance_for_rent_exemption(self, data_len: int) -> int: ...
     def get_account(self, pubkey: Pubkey) -> Optional[Account]: ...
+    def accounts(self) -> List[Tuple[Pubkey, Account]]: ...
+    def get_program_accounts(
+        self,
+        program_id: Pubkey,
+        filters: Optional[Sequence[Union[int, Memcmp, RpcFilterTypeFieldless]]] = None,
+    ) -> List[Tuple[Pubkey, Account]]: ...
     def set_account(self, pubkey: Pubkey, data: Account) -> None: ...
+    def load_account_from_json(self, path: Path) -> Pubkey: ...
+    def load_accounts_from_dir(self, path: Path) -> List[Pubkey]: ...
+    def add_accounts_from_rpc_response(
+        self,
+        resp: Union[
+            GetAccountInfoResp,
+            GetMultipleAccountsResp,
+            GetProgramAccountsResp,
+            GetProgramAccountsWithContextResp,
+        ],
+        addresses: Optional[Sequence[Pubkey]] = None,
+    ) -> List[Pubkey]: ...
+    def save_account_to_json(self, pubkey: Pubkey, path: Path) -> None: ...
+    def save_accounts_to_dir(self, pubkeys: Sequence[Pubkey], path: Path) -> None: ...
+    def create_mint(
+        self,
+        authority: Pubkey,
+        decimals: int,
+        mint: Optional[Pubkey] = None,
+        freeze_authority: Optional[Pubkey] = None,
+        token_program_id: Optional[Pubkey] = None,
+    ) -> Pubkey: ...
+    def create_ata(self, owner: Pubkey, mint: Pubkey) -> Pubkey: ...
+    def mint_to(self, mint: Pubkey, destination: Pubkey, amount: int) -> None: ...
+    def set_token_balance(self, account: Pubkey, amount: int) -> None: ...
     def get_balance(self, pubkey: Pubkey) -> Optional[int]: ...
     def latest_blockhash(self) -> Hash: ...
     def get_transaction(self, signature: Signature) -> Optional[TransactionResult]: ...
     def airdrop(self, pubkey: Pubkey, lamports: int) -> TransactionResult: ...
+    def new_keypair(self) -> Keypair: ...
+    def get_signatures_for_address(
+        self,
+        address: Pubkey,
+        before: Optional[Signature] = None,
+        until: Optional[Signature] = None,
+        limit: Optional[int] = None,
+    ) -> List[RpcConfirmedTransactionStatusWithSignature]: ...
+    def get_signature_statuses(
+        self, signatures: Sequence[Signature]
+    ) -> List[Optional[TransactionStatus]]: ...
+    def add_mock_program(
+        self,
+        program_id: Pubkey,
+        callback: Callable[
+            [Pubkey, List[AccountInfo], bytes],
+            Union[None, bytes, InstructionErrorType],
+        ],
+    ) -> None: ...
     def add_program_from_file(self, program_id: Pubkey, path: Path) -> None: ...
     def add_program(
         self, program_id: Pubkey, program_bytes: Union[bytes, Sequence[int]]
     ) -> None: ...
+    def add_upgradeable_program(
+        self,
+        program_id: Pubkey,
+        program_bytes: Union[bytes, Sequence[int]],
+        upgrade_authority: Optional[Pubkey] = None,
+    ) -> None: ...
+    def upgrade_program(
+        self,
+        program_id: Pubkey,
+        program_bytes: Union[bytes, Sequence[int]],
+        upgrade_authority: Keypair,
+        payer: Optional[Keypair] = None,
+    ) -> TransactionResult: ...
+    def create_lookup_table(
+        self,
+        authority: Keypair,
+        addresses: Sequence[Pubkey],
+        payer: Optional[Keypair] = None,
+    ) -> Pubkey: ...
     def send_transaction(
         self, tx: Union[Transaction, VersionedTransaction]
     ) -> TransactionResult: ...
+    def send_transactions(
+        self,
+        txs: Sequence[Union[Transaction, VersionedTransaction]],
+        atomic: bool = False,
+    ) -> List[TransactionResult]: ...
+    @staticmethod
+    def send_transactions_parallel(
+        jobs: Sequence[
+            Tuple["LiteSVM", Sequence[Union[Transaction, VersionedTransaction]]]
+        ],
+        atomic: bool = False,
+        max_workers: Optional[int] = None,
+    ) -> List[List[TransactionResult]]: ...
     def simulate_transaction(
         self, tx: Union[Transaction, VersionedTransaction]
     ) -> SimulateResult: ...
+    def on_transaction(
+        self, callback: Callable[[TransactionResult, List[Tuple[Pubkey, Account]]], Any]
+    ) -> None: ...
+    def on_account_change(
+        self, pubkey: Pubkey, callback: Callable[[Pubkey, Account], Any]
+    ) -> None: ...
+    def fork(self) -> "LiteSVM": ...
+    def snapshot(self) -> LiteSVMSnapshot: ...
+    def restore(self, snapshot: LiteSVMSnapshot) -> None: ...
     def expire_blockhash(self) -> None: ...
     def warp_to_slot(self, slot: int) -> None: ...
+    def warp_to_epoch(self, epoch: int, reward_rate: Optional[float] = None) -> None: ...
     def get_compute_budget(self) -> Optional[ComputeBudget]: ...
     def get_clock(self) -> Clock: ...
     def set_clock(self, clock: Clock) -> None: ...
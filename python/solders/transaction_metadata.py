@@ -1,8 +1,12 @@
 from typing import Union
 
 from .solders import (
+    AccountDiff,
     FailedTransactionMetadata,
+    FeeReport,
     InnerInstruction,
+    InstructionComputeUnits,
+    ProgramTrace,
     SimulatedTransactionInfo,
     TransactionMetadata,
 )
@@ -11,7 +15,11 @@ SimulateResult = Union[SimulatedTransactionInfo, FailedTransactionMetadata]
 TransactionResult = Union[TransactionMetadata, FailedTransactionMetadata]
 
 __all__ = [
+    "AccountDiff",
+    "FeeReport",
     "InnerInstruction",
+    "InstructionComputeUnits",
+    "ProgramTrace",
     "TransactionMetadata",
     "FailedTransactionMetadata",
     "SimulatedTransactionInfo",
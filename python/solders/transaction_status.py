@@ -8,6 +8,8 @@ from .solders import (
     InstructionErrorFieldless,
     ParsedAccountSource,
     ParsedInstruction,
+    ProgramInvocation,
+    ProgramLogs,
     Reward,
     RewardType,
     TransactionBinaryEncoding,
@@ -82,6 +84,8 @@ __all__ = [
     "TransactionStatus",
     "EncodedConfirmedTransactionWithStatusMeta",
     "UiConfirmedBlock",
+    "ProgramInvocation",
+    "ProgramLogs",
     "InstructionErrorFieldless",
     "InstructionErrorCustom",
     "InstructionErrorBorshIO",
@@ -0,0 +1,137 @@
+import pytest
+
+from solders.instruction import AccountMeta, Instruction
+from solders.keypair import Keypair
+from solders.litesvm import LiteSVM
+from solders.litesvm_fuzz import (
+    INVARIANT,
+    PANIC,
+    TRANSACTION_ERROR,
+    Action,
+    Fuzzer,
+    binary,
+    integers,
+    sampled_from,
+)
+from solders.pubkey import Pubkey
+from solders.system_program import transfer
+from solders.transaction_status import (
+    InstructionErrorCustom,
+    TransactionErrorInstructionError,
+)
+
+LAMPORTS_PER_SOL = 1_000_000_000
+
+
+def fuzz_setup() -> tuple:
+    svm = LiteSVM()
+    payer = Keypair()
+    svm.airdrop(payer.pubkey(), 10 * LAMPORTS_PER_SOL)
+    receivers = [Pubkey.new_unique(), Pubkey.new_unique()]
+    for receiver in receivers:
+        svm.airdrop(receiver, LAMPORTS_PER_SOL)
+    return svm, payer, receivers
+
+
+def transfer_action(payer: Keypair, receivers: list, max_lamports: int) -> Action:
+    return Action(
+        "transfer",
+        lambda to, lamports: transfer(
+            {"from_pubkey": payer.pubkey(), "to_pubkey": to, "lamports": lamports}
+        ),
+        {"to": sampled_from(receivers), "lamports": integers(1, max_lamports)},
+    )
+
+
+def test_transaction_error_is_shrunk() -> None:
+    svm, payer, receivers = fuzz_setup()
+    payer_balance = svm.get_balance(payer.pubkey())
+    action = transfer_action(payer, receivers, 20 * LAMPORTS_PER_SOL)
+    failure = Fuzzer(svm, payer, [action], seed=7).run()
+    assert failure is not None
+    assert failure.kind == TRANSACTION_ERROR
+    assert failure.seed == 7
+    assert len(failure.steps) == 1
+    assert len(failure.original_steps) >= 1
+    # The smallest transfer the payer can't afford after paying the fee.
+    assert failure.steps[0].args == {
+        "to": receivers[0],
+        "lamports": payer_balance - 4999,
+    }
+    assert isinstance(failure.error, TransactionErrorInstructionError)
+    assert failure.instruction_error == InstructionErrorCustom(1)
+    assert str(failure).startswith("transaction failed")
+    assert "transfer(to=" in str(failure)
+    # The fuzzed instance is left as it was.
+    assert svm.get_balance(payer.pubkey()) == payer_balance
+
+
+def test_invariant_violation() -> None:
+    svm, payer, receivers = fuzz_setup()
+
+    def capped(svm: LiteSVM) -> None:
+        balance = svm.get_balance(receivers[1])
+        assert balance is not None
+        assert balance <= 3 * LAMPORTS_PER_SOL, "receiver balance too high"
+
+    action = transfer_action(payer, receivers, LAMPORTS_PER_SOL)
+    failure = Fuzzer(svm, payer, [action], invariants=[capped], seed=3).run(
+        iterations=200
+    )
+    assert failure is not None
+    assert failure.kind == INVARIANT
+    assert isinstance(failure.exception, AssertionError)
+    # Three transfers of up to 1 SOL are needed to exceed the cap.
+    assert len(failure.steps) == 3
+    assert all(step.args["to"] == receivers[1] for step in failure.steps)
+    total = sum(step.args["lamports"] for step in failure.steps)
+    assert total == 2 * LAMPORTS_PER_SOL + 1
+
+
+def test_panic_and_expected_errors() -> None:
+    svm, payer, receivers = fuzz_setup()
+    program_id = Pubkey.new_unique()
+
+    def build_call(data: bytes) -> Instruction:
+        if len(data) > 8:
+            raise ValueError("instruction data too long")
+        return Instruction(program_id, data, [AccountMeta(receivers[0], False, True)])
+
+    # Calls to the missing program fail, which is expected here.
+    fuzzer = Fuzzer(
+        svm,
+        payer,
+        [Action("call", build_call, {"data": binary(0, 16)})],
+        expected_error=lambda step, _err: step.action.name == "call",
+        seed=1,
+    )
+    failure = fuzzer.run()
+    assert failure is not None
+    assert failure.kind == PANIC
+    assert isinstance(failure.exception, ValueError)
+    assert failure.result is None
+    assert [step.args for step in failure.steps] == [{"data": bytes(9)}]
+    fuzzer.actions = [
+        Action("call", build_call, {"data": binary(0, 8)}),
+        transfer_action(payer, receivers, 1000),
+    ]
+    assert fuzzer.run(iterations=20) is None
+    assert svm.get_balance(receivers[0]) == LAMPORTS_PER_SOL
+
+
+def test_undeclared_signer_is_not_a_panic() -> None:
+    svm, payer, receivers = fuzz_setup()
+    sender = Keypair()
+    svm.airdrop(sender.pubkey(), LAMPORTS_PER_SOL)
+    action = Action(
+        "transfer_from_sender",
+        lambda: transfer(
+            {"from_pubkey": sender.pubkey(), "to_pubkey": receivers[0], "lamports": 1}
+        ),
+    )
+    fuzzer = Fuzzer(svm, payer, [action], seed=3)
+    with pytest.raises(ValueError, match="transfer_from_sender"):
+        fuzzer.run(iterations=1)
+    assert svm.get_balance(receivers[0]) == LAMPORTS_PER_SOL
+    action.signers = [sender]
+    assert fuzzer.run(iterations=5) is None
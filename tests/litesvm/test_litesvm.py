@@ -1,19 +1,70 @@
+import struct
 from dataclasses import dataclass
 from pathlib import Path
-from typing import Optional, Tuple
+from typing import List, Optional, Tuple, Union
+
+import pytest
 
 from solders.account import Account
+from solders.address_lookup_table_account import ID as ADDRESS_LOOKUP_TABLE_ID
+from solders.address_lookup_table_account import (
+    AddressLookupTable,
+    AddressLookupTableAccount,
+)
 from solders.clock import Clock
-from solders.compute_budget import ComputeBudget
+from solders.compute_budget import (
+    ComputeBudget,
+    set_compute_unit_limit,
+    set_compute_unit_price,
+)
+from solders.hash import Hash
 from solders.instruction import AccountMeta, Instruction
 from solders.keypair import Keypair
-from solders.litesvm import LiteSVM
-from solders.message import Message
+from solders.litesvm import AccountInfo, FeatureSet, LiteSVM
+from solders.message import Message, MessageV0
 from solders.pubkey import Pubkey
 from solders.rent import Rent
-from solders.system_program import transfer
+from solders.rpc.filter import Memcmp, RpcFilterTypeFieldless
+from solders.rpc.responses import (
+    GetMultipleAccountsResp,
+    GetProgramAccountsResp,
+    RpcKeyedAccount,
+    RpcResponseContext,
+)
+from solders.system_program import ID as SYSTEM_PROGRAM_ID
+from solders.system_program import (
+    AdvanceNonceAccountParams,
+    advance_nonce_account,
+    close_lookup_table,
+    create_nonce_account,
+    deactivate_lookup_table,
+    extend_lookup_table,
+    transfer,
+)
+from solders.token import ID as TOKEN_PROGRAM_ID
+from solders.token.associated import get_associated_token_address
+from solders.token.state import Mint, TokenAccount, TokenAccountState
 from solders.transaction import Transaction, VersionedTransaction
-from solders.transaction_metadata import FailedTransactionMetadata, TransactionMetadata
+from solders.transaction_metadata import (
+    FailedTransactionMetadata,
+    SimulatedTransactionInfo,
+    TransactionMetadata,
+    TransactionResult,
+)
+from solders.transaction_status import (
+    InstructionErrorCustom,
+    InstructionErrorFieldless,
+    InstructionErrorType,
+    ProgramLogs,
+    TransactionConfirmationStatus,
+    TransactionErrorFieldless,
+    TransactionErrorInstructionError,
+    TransactionReturnData,
+)
+
+ASSOCIATED_TOKEN_PROGRAM_ID = Pubkey.from_string(
+    "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+)
 
 
 def helloworld_program(
@@ -113,6 +164,143 @@ def test_helloworld_legacy_tx() -> None:
     assert greeted_account_after.data == bytes([1, 0, 0, 0])
 
 
+def test_compute_units_profile() -> None:
+    setup = helloworld_setup()
+    payer = setup.payer
+    client = setup.client
+    ix = setup.msg.instructions[0]
+    program_id = setup.msg.account_keys[ix.program_id_index]
+    greet = Instruction(
+        program_id,
+        bytes([0]),
+        [AccountMeta(setup.greeted_pubkey, is_signer=False, is_writable=True)],
+    )
+    transfer_ix = transfer(
+        {
+            "from_pubkey": payer.pubkey(),
+            "to_pubkey": Pubkey.new_unique(),
+            "lamports": 1_000_000,
+        }
+    )
+    msg = Message.new_with_blockhash(
+        [greet, transfer_ix], payer.pubkey(), client.latest_blockhash()
+    )
+    res = client.send_transaction(Transaction([payer], msg, msg.recent_blockhash))
+    assert isinstance(res, TransactionMetadata)
+    profile = res.compute_units_profile()
+    assert [node.program_id for node in profile] == [program_id, transfer_ix.program_id]
+    greet_node, transfer_node = profile
+    assert greet_node.stack_height == 1
+    assert greet_node.children == []
+    assert greet_node.consumed is not None
+    assert greet_node.exclusive_consumed() == greet_node.consumed
+    assert greet_node.budget is not None and greet_node.budget >= greet_node.consumed
+    # builtins don't log their consumption
+    assert transfer_node.consumed is None
+    assert res.compute_units_consumed() >= greet_node.consumed
+
+
+def test_compute_units_profile_cpi() -> None:
+    client = LiteSVM()
+    payer = Keypair()
+    client.airdrop(payer.pubkey(), 1_000_000_000)
+    mint = Pubkey.new_unique()
+    client.set_account(
+        mint,
+        Account(
+            lamports=1_000_000_000,
+            data=bytes(Mint(payer.pubkey(), 0, 6, True)),
+            owner=TOKEN_PROGRAM_ID,
+            executable=False,
+        ),
+    )
+    owner = Pubkey.new_unique()
+    create_ata = Instruction(
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+        b"",
+        [
+            AccountMeta(payer.pubkey(), is_signer=True, is_writable=True),
+            AccountMeta(
+                get_associated_token_address(owner, mint),
+                is_signer=False,
+                is_writable=True,
+            ),
+            AccountMeta(owner, is_signer=False, is_writable=False),
+            AccountMeta(mint, is_signer=False, is_writable=False),
+            AccountMeta(SYSTEM_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    # An Ed25519 instruction with no signatures, which doesn't log an invocation.
+    verify = Instruction(
+        Pubkey.from_string("Ed25519SigVerify111111111111111111111111111"),
+        bytes([0, 0]),
+        [],
+    )
+    msg = Message.new_with_blockhash(
+        [verify, create_ata], payer.pubkey(), client.latest_blockhash()
+    )
+    res = client.send_transaction(VersionedTransaction(msg, [payer]))
+    assert isinstance(res, TransactionMetadata)
+    [ata_node] = res.compute_units_profile()
+    assert ata_node.program_id == ASSOCIATED_TOKEN_PROGRAM_ID
+    assert {cpi.program_id for cpi in ata_node.children} == {
+        SYSTEM_PROGRAM_ID,
+        TOKEN_PROGRAM_ID,
+    }
+    for cpi in ata_node.children:
+        assert cpi.stack_height == 2
+        assert cpi.instruction is not None
+        assert msg.account_keys[cpi.instruction.program_id_index] == cpi.program_id
+    # The System program is a builtin, so its CPIs count as zero.
+    assert ata_node.consumed is not None
+    token_consumed = sum(cpi.consumed or 0 for cpi in ata_node.children)
+    assert ata_node.exclusive_consumed() == ata_node.consumed - token_consumed
+
+
+def test_parsed_logs() -> None:
+    setup = helloworld_setup()
+    msg = setup.msg
+    program_id = msg.account_keys[msg.instructions[0].program_id_index]
+    tx = Transaction([setup.payer], msg, msg.recent_blockhash)
+    res = setup.client.send_transaction(tx)
+    assert isinstance(res, TransactionMetadata)
+    parsed = res.parsed_logs()
+    assert not parsed.truncated
+    [invocation] = parsed.invocations
+    assert invocation.program_id == program_id
+    assert invocation.depth == 1
+    assert invocation.success
+    assert invocation.error is None
+    assert invocation.logs
+    assert invocation.consumed == res.compute_units_consumed()
+    caller, callee = Pubkey.new_unique(), Pubkey.new_unique()
+    nested = ProgramLogs(
+        [
+            f"Program {caller} invoke [1]",
+            f"Program {callee} invoke [2]",
+            "Program data: aGVsbG8= d29ybGQ=",
+            f"Program return: {callee} AQI=",
+            f"Program {callee} consumed 100 of 1000 compute units",
+            f"Program {callee} success",
+            "Program log: oops",
+            f"Program {caller} consumed 300 of 1200 compute units",
+            f"Program {caller} failed: custom program error: 0x1",
+        ]
+    )
+    [outer] = nested.invocations
+    assert outer.logs == ["oops"]
+    assert (outer.success, outer.error) == (False, "custom program error: 0x1")
+    [inner] = outer.children
+    assert inner.depth == 2
+    assert inner.data == [[b"hello", b"world"]]
+    assert inner.return_data == TransactionReturnData(callee, b"\x01\x02")
+    assert (inner.consumed, inner.budget) == (100, 1000)
+    truncated = ProgramLogs([f"Program {caller} invoke [1]", "Log truncated"])
+    assert truncated.truncated
+    assert truncated.invocations[0].success is None
+
+
 def test_compute_limit() -> None:
     client, program_id, greeted_pubkey = helloworld_program(compute_max_units=10)
     ix = Instruction(
@@ -129,6 +317,105 @@ def test_compute_limit() -> None:
     assert isinstance(res, FailedTransactionMetadata)
 
 
+def test_tracing() -> None:
+    client, program_id, greeted_pubkey = helloworld_program()
+    client.with_tracing()
+    payer = Keypair()
+    client.airdrop(payer.pubkey(), 1_000_000_000)
+    greet = Instruction(
+        program_id,
+        bytes([0]),
+        [AccountMeta(greeted_pubkey, is_signer=False, is_writable=True)],
+    )
+
+    def greet_tx(compute_unit_limit: int) -> VersionedTransaction:
+        client.expire_blockhash()
+        ixs = [set_compute_unit_limit(compute_unit_limit), greet]
+        msg = Message.new_with_blockhash(ixs, payer.pubkey(), client.latest_blockhash())
+        return VersionedTransaction(msg, [payer])
+
+    sim = client.simulate_transaction(greet_tx(200_000))
+    assert isinstance(sim, SimulatedTransactionInfo)
+    assert sim.meta().traces() is not None
+    # Simulations don't count towards coverage.
+    assert client.coverage() == {}
+    res = client.send_transaction(greet_tx(200_000))
+    assert isinstance(res, TransactionMetadata)
+    traces = res.traces()
+    assert traces is not None
+    # The compute budget program is a builtin, so it isn't traced.
+    [trace] = traces
+    assert trace.program_id == program_id
+    assert trace.stack_height == 1
+    pcs = trace.pcs()
+    registers = trace.registers()
+    assert len(pcs) == len(registers) == len(trace) > 0
+    # The entrypoint gets the address of the input region in r1.
+    assert registers[0][1] == 0x400000000
+    hits = client.coverage()[program_id]
+    assert sum(hits.values()) == len(pcs)
+    assert {pc for pc, count in hits.items() if count} == set(pcs)
+    assert 0 in hits.values()
+    report = client.coverage_report().splitlines()
+    assert report == [f"{program_id} {pc} {n}" for pc, n in sorted(hits.items())]
+    # Restoring a snapshot rolls coverage back too.
+    snapshot = client.snapshot()
+    client.send_transaction(greet_tx(200_000))
+    assert sum(client.coverage()[program_id].values()) == 2 * len(pcs)
+    client.restore(snapshot)
+    assert client.coverage()[program_id] == hits
+    # A failed invocation keeps the instructions it executed.
+    failed = client.send_transaction(greet_tx(300))
+    assert isinstance(failed, FailedTransactionMetadata)
+    failed_traces = failed.meta().traces()
+    assert failed_traces is not None
+    assert 0 < len(failed_traces[0]) < len(trace)
+    assert sum(client.coverage()[program_id].values()) > len(pcs)
+    client.reset_coverage()
+    assert client.coverage() == {}
+    client.with_tracing(False)
+    res = client.send_transaction(greet_tx(200_000))
+    assert isinstance(res, TransactionMetadata)
+    assert res.traces() is None
+    assert client.coverage() == {}
+
+
+def test_tracing_cpi() -> None:
+    client = LiteSVM().with_tracing()
+    payer = Keypair()
+    client.airdrop(payer.pubkey(), 1_000_000_000)
+    mint = client.create_mint(payer.pubkey(), 6)
+    owner = Pubkey.new_unique()
+    ata = get_associated_token_address(owner, mint)
+    create_ata = Instruction(
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+        b"",
+        [
+            AccountMeta(payer.pubkey(), is_signer=True, is_writable=True),
+            AccountMeta(ata, is_signer=False, is_writable=True),
+            AccountMeta(owner, is_signer=False, is_writable=False),
+            AccountMeta(mint, is_signer=False, is_writable=False),
+            AccountMeta(SYSTEM_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    msg = Message.new_with_blockhash(
+        [create_ata], payer.pubkey(), client.latest_blockhash()
+    )
+    res = client.send_transaction(VersionedTransaction(msg, [payer]))
+    assert isinstance(res, TransactionMetadata)
+    traces = res.traces()
+    assert traces is not None
+    # The token program's CPIs are traced after the invocation that made them.
+    assert traces[0].program_id == ASSOCIATED_TOKEN_PROGRAM_ID
+    assert traces[0].stack_height == 1
+    cpis = traces[1:]
+    assert cpis
+    assert {trace.program_id for trace in cpis} == {TOKEN_PROGRAM_ID}
+    assert {trace.stack_height for trace in cpis} == {2}
+    assert set(client.coverage()) == {ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID}
+
+
 def test_sysvar() -> None:
     client = LiteSVM()
     rent_before = client.get_rent()
@@ -172,6 +459,93 @@ def test_warp() -> None:
     assert slot1 == new_slot
 
 
+def test_auto_advance() -> None:
+    client = LiteSVM().with_auto_advance(slot_duration_ms=600)
+    payer = Keypair()
+    client.airdrop(payer.pubkey(), 1_000_000_000)
+    start = client.get_clock()
+    blockhashes = [client.latest_blockhash()]
+    for i in range(2):
+        ix = transfer(
+            {
+                "from_pubkey": payer.pubkey(),
+                "to_pubkey": Pubkey.new_unique(),
+                "lamports": 1,
+            }
+        )
+        msg = Message.new_with_blockhash(
+            [ix], payer.pubkey(), client.latest_blockhash()
+        )
+        res = client.send_transaction(VersionedTransaction(msg, [payer]))
+        assert isinstance(res, TransactionMetadata)
+        blockhashes.append(client.latest_blockhash())
+    clock = client.get_clock()
+    assert clock.slot == start.slot + 2
+    assert clock.unix_timestamp == start.unix_timestamp + 1
+    assert len(set(blockhashes)) == 3
+    slot_hashes = client.get_slot_hashes()
+    assert slot_hashes[:2] == [
+        (start.slot + 1, blockhashes[1]),
+        (start.slot, blockhashes[0]),
+    ]
+    assert client.get_slot_history().newest() == start.slot + 2
+    # a transaction signed against a rotated-out blockhash is rejected
+    stale = Message.new_with_blockhash([], payer.pubkey(), blockhashes[0])
+    assert isinstance(
+        client.send_transaction(VersionedTransaction(stale, [payer])),
+        FailedTransactionMetadata,
+    )
+
+
+def stake_account_data(
+    staker: Pubkey, voter: Pubkey, stake: int, activation_epoch: int
+) -> bytes:
+    # bincode layout of StakeStateV2::Stake
+    meta = struct.pack("<Q", 2_282_880) + bytes(staker) + bytes(staker)
+    meta += struct.pack("<qQ", 0, 0) + bytes(Pubkey.default())
+    delegation = bytes(voter) + struct.pack(
+        "<QQQd", stake, activation_epoch, 2**64 - 1, 0.25
+    )
+    data = struct.pack("<I", 2) + meta + delegation + struct.pack("<QB", 0, 0)
+    return data.ljust(200, b"\0")
+
+
+def test_warp_to_epoch() -> None:
+    client = LiteSVM()
+    stake_program = Pubkey.from_string("Stake11111111111111111111111111111111111111")
+    voter, staker = Pubkey.new_unique(), Pubkey.new_unique()
+    bootstrap, activating = Pubkey.new_unique(), Pubkey.new_unique()
+    stake = 1_000_000_000_000
+    for pubkey, activation_epoch in [(bootstrap, 2**64 - 1), (activating, 0)]:
+        data = stake_account_data(staker, voter, stake, activation_epoch)
+        client.set_account(pubkey, Account(stake + 2_282_880, data, stake_program))
+    with pytest.raises(ValueError):
+        client.warp_to_epoch(0)
+    for reward_rate in (-0.1, 1.5, float("nan")):
+        with pytest.raises(ValueError):
+            client.warp_to_epoch(3, reward_rate=reward_rate)
+    client.warp_to_epoch(3, reward_rate=0.001)
+    clock = client.get_clock()
+    schedule = client.get_epoch_schedule()
+    assert clock.epoch == 3
+    assert clock.slot == schedule.get_first_slot_in_epoch(3)
+    assert clock.leader_schedule_epoch == schedule.get_leader_schedule_epoch(clock.slot)
+    history = client.get_stake_history()
+    entries = [history.get(epoch) for epoch in range(3)]
+    assert all(entry is not None for entry in entries)
+    assert entries[0].activating == stake
+    assert entries[0].effective == stake
+    assert 0 < entries[1].activating < stake
+    assert entries[1].effective > entries[0].effective
+    rewards = client.get_epoch_rewards()
+    assert not rewards.active
+    assert rewards.total_rewards == rewards.distributed_rewards > 0
+    bootstrap_account = client.get_account(bootstrap)
+    assert bootstrap_account is not None
+    assert bootstrap_account.lamports > stake + 2_282_880
+    assert struct.unpack_from("<Q", bootstrap_account.data, 156)[0] > stake
+
+
 def test_many_instructions() -> None:
     # https://github.com/solana-labs/example-helloworld/blob/36eb41d1290732786e13bd097668d8676254a139/src/program-rust/tests/lib.rs
     client, program_id, greeted_pubkey = helloworld_program()
@@ -230,6 +604,536 @@ def test_transfer() -> None:
     )
 
 
+def seeded_run(seed: Optional[int]) -> tuple:
+    client = LiteSVM(seed=seed)
+    payer = client.new_keypair()
+    receiver = client.new_keypair().pubkey()
+    airdrop_res = client.airdrop(payer.pubkey(), 1_000_000_000)
+    assert isinstance(airdrop_res, TransactionMetadata)
+    mint = client.create_mint(payer.pubkey(), 6)
+    ix = transfer(
+        {"from_pubkey": payer.pubkey(), "to_pubkey": receiver, "lamports": 1_000_000}
+    )
+    msg = Message.new_with_blockhash([ix], payer.pubkey(), client.latest_blockhash())
+    res = client.send_transaction(VersionedTransaction(msg, [payer]))
+    assert isinstance(res, TransactionMetadata)
+    return (
+        [airdrop_res.signature(), res.signature()],
+        res.logs(),
+        mint,
+        list(client.accounts()),
+    )
+
+
+def test_seeded_runs() -> None:
+    signatures, logs, mint, accounts = seeded_run(42)
+    assert seeded_run(42) == (signatures, logs, mint, accounts)
+    other_signatures, other_logs, other_mint, _ = seeded_run(43)
+    assert other_logs == logs
+    assert set(other_signatures).isdisjoint(signatures)
+    assert other_mint != mint
+    # Without a seed, airdrops are paid by a random keypair.
+    unseeded_signatures, _, _, _ = seeded_run(None)
+    assert seeded_run(None)[0] != unseeded_signatures
+    assert LiteSVM(seed=1).new_keypair() == LiteSVM(seed=1).new_keypair()
+    blockhashes = {LiteSVM(seed=seed).latest_blockhash() for seed in (1, 1, 2)}
+    assert len(blockhashes | {LiteSVM().latest_blockhash()}) == 3
+    client = LiteSVM(seed=1)
+    snapshot = client.snapshot()
+    keypair = client.new_keypair()
+    client.restore(snapshot)
+    assert client.new_keypair() == keypair
+
+
+def test_account_diffs() -> None:
+    client = LiteSVM().with_account_diffs()
+    payer = Keypair()
+    client.airdrop(payer.pubkey(), 1_000_000_000)
+    owner = Keypair()
+    mint = Pubkey.new_unique()
+    source, dest = Pubkey.new_unique(), Pubkey.new_unique()
+    rent = client.minimum_balance_for_rent_exemption(165)
+    for pubkey, amount in [(source, 100), (dest, 0)]:
+        token_account = TokenAccount(
+            mint=mint,
+            owner=owner.pubkey(),
+            amount=amount,
+            delegate=None,
+            state=TokenAccountState.Initialized,
+            is_native=None,
+            delegated_amount=0,
+        )
+        client.set_account(
+            pubkey, Account(rent, bytes(token_account), TOKEN_PROGRAM_ID)
+        )
+    client.set_account(
+        mint,
+        Account(
+            client.minimum_balance_for_rent_exemption(82),
+            bytes(Mint(None, 100, 0, True)),
+            TOKEN_PROGRAM_ID,
+        ),
+    )
+    token_transfer = Instruction(
+        TOKEN_PROGRAM_ID,
+        bytes([3]) + (40).to_bytes(8, "little"),
+        [
+            AccountMeta(source, is_signer=False, is_writable=True),
+            AccountMeta(dest, is_signer=False, is_writable=True),
+            AccountMeta(owner.pubkey(), is_signer=True, is_writable=False),
+        ],
+    )
+    msg = Message.new_with_blockhash(
+        [token_transfer], payer.pubkey(), client.latest_blockhash()
+    )
+    tx = VersionedTransaction(msg, [payer, owner])
+    sim = client.simulate_transaction(tx)
+    assert isinstance(sim, SimulatedTransactionInfo)
+    res = client.send_transaction(tx)
+    assert isinstance(res, TransactionMetadata)
+    diffs = res.account_diffs()
+    assert diffs is not None
+    assert sim.meta().account_diffs() == diffs
+    by_key = {diff.pubkey: diff for diff in diffs}
+    assert set(by_key) == {payer.pubkey(), source, dest}
+    assert by_key[payer.pubkey()].lamports_delta() == -10_000
+    assert by_key[payer.pubkey()].changed_data == []
+    assert by_key[source].token_amount_delta() == -40
+    assert by_key[dest].token_amount_delta() == 40
+    assert by_key[dest].changed_data == [(64, 65)]
+    assert by_key[dest].pre_data_len == by_key[dest].post_data_len == 165
+    client.with_account_diffs(False)
+    client.expire_blockhash()
+    msg = Message.new_with_blockhash(
+        [token_transfer], payer.pubkey(), client.latest_blockhash()
+    )
+    res = client.send_transaction(VersionedTransaction(msg, [payer, owner]))
+    assert isinstance(res, TransactionMetadata)
+    assert res.account_diffs() is None
+
+
+def ed25519_instruction(signer: Keypair, message: bytes) -> Instruction:
+    # One signature, with the public key, signature and message
+    # all stored in this instruction's data after the offsets.
+    current_ix = 0xFFFF
+    offsets = struct.pack(
+        "<7H", 48, current_ix, 16, current_ix, 112, len(message), current_ix
+    )
+    data = (
+        bytes([1, 0])
+        + offsets
+        + bytes(signer.pubkey())
+        + bytes(signer.sign_message(message))
+        + message
+    )
+    return Instruction(
+        Pubkey.from_string("Ed25519SigVerify111111111111111111111111111"), data, []
+    )
+
+
+def test_fee_report() -> None:
+    client = LiteSVM()
+    payer = Keypair()
+    client.airdrop(payer.pubkey(), 1_000_000_000)
+    ixs = [
+        set_compute_unit_limit(20_000),
+        set_compute_unit_price(150_000),
+        ed25519_instruction(Keypair(), b"hello"),
+    ]
+    msg = Message.new_with_blockhash(ixs, payer.pubkey(), client.latest_blockhash())
+    tx = VersionedTransaction(msg, [payer])
+    sim = client.simulate_transaction(tx)
+    assert isinstance(sim, SimulatedTransactionInfo)
+    balance_before = client.get_balance(payer.pubkey())
+    res = client.send_transaction(tx)
+    assert isinstance(res, TransactionMetadata)
+    report = res.fee_report()
+    assert report is not None
+    assert sim.meta().fee_report() == report
+    assert report.payer == payer.pubkey()
+    assert report.lamports_per_signature == 5000
+    assert report.signature_fee == 5000
+    assert report.precompile_signature_fee == 5000
+    assert report.compute_unit_limit == 20_000
+    assert report.compute_unit_price == 150_000
+    assert report.priority_fee == 3000
+    assert report.total() == 13_000
+    # LiteSVM doesn't deduct the priority fee.
+    assert balance_before is not None
+    assert report.payer_balance == client.get_balance(payer.pubkey())
+    assert balance_before - report.payer_balance == 10_000
+    rent_exempt_minimum = client.minimum_balance_for_rent_exemption(0)
+    assert report.payer_rent_exempt_minimum == rent_exempt_minimum
+    assert not report.payer_below_rent_exempt_minimum()
+    # A payer that is already below the rent-exempt minimum can still pay fees.
+    poor_payer = Keypair()
+    client.set_account(poor_payer.pubkey(), Account(100_000, b"", SYSTEM_PROGRAM_ID))
+    ix = transfer(
+        {
+            "from_pubkey": poor_payer.pubkey(),
+            "to_pubkey": Pubkey.new_unique(),
+            "lamports": 1_000_000,
+        }
+    )
+    msg = Message.new_with_blockhash(
+        [ix], poor_payer.pubkey(), client.latest_blockhash()
+    )
+    failed = client.send_transaction(VersionedTransaction(msg, [poor_payer]))
+    assert isinstance(failed, FailedTransactionMetadata)
+    report = failed.meta().fee_report()
+    assert report is not None
+    assert report.priority_fee == 0
+    assert report.payer_balance == 95_000
+    assert report.payer_below_rent_exempt_minimum()
+
+
+def test_callbacks() -> None:
+    client = LiteSVM()
+    payer = Keypair()
+    client.airdrop(payer.pubkey(), 1_000_000_000)
+    owner = Keypair()
+    mint = client.create_mint(owner.pubkey(), 0)
+    source = client.create_ata(owner.pubkey(), mint)
+    dest = client.create_ata(Pubkey.new_unique(), mint)
+    client.mint_to(mint, source, 100)
+    results = []
+    dest_states = []
+
+    def check_supply(
+        result: TransactionResult, changed_accounts: List[Tuple[Pubkey, Account]]
+    ) -> None:
+        results.append((result, [pubkey for pubkey, _ in changed_accounts]))
+        mint_account = client.get_account(mint)
+        assert mint_account is not None
+        total = sum(
+            TokenAccount.from_bytes(account.data[:165]).amount
+            for _, account in client.get_program_accounts(TOKEN_PROGRAM_ID, [165])
+        )
+        assert total == Mint.from_bytes(mint_account.data).supply
+
+    client.on_transaction(check_supply)
+    client.on_account_change(dest, lambda _pubkey, acc: dest_states.append(acc))
+    token_transfer = Instruction(
+        TOKEN_PROGRAM_ID,
+        bytes([3]) + (40).to_bytes(8, "little"),
+        [
+            AccountMeta(source, is_signer=False, is_writable=True),
+            AccountMeta(dest, is_signer=False, is_writable=True),
+            AccountMeta(owner.pubkey(), is_signer=True, is_writable=False),
+        ],
+    )
+    msg = Message.new_with_blockhash(
+        [token_transfer], payer.pubkey(), client.latest_blockhash()
+    )
+    res = client.send_transaction(VersionedTransaction(msg, [payer, owner]))
+    assert isinstance(res, TransactionMetadata)
+    assert len(results) == 1
+    assert results[0][0] == res
+    assert set(results[0][1]) == {payer.pubkey(), source, dest}
+    assert len(dest_states) == 1
+    assert TokenAccount.from_bytes(dest_states[0].data).amount == 40
+    client.expire_blockhash()
+    overdraw = Instruction(
+        TOKEN_PROGRAM_ID,
+        bytes([3]) + (1_000).to_bytes(8, "little"),
+        token_transfer.accounts,
+    )
+    msg = Message.new_with_blockhash(
+        [overdraw], payer.pubkey(), client.latest_blockhash()
+    )
+    failed = client.send_transaction(VersionedTransaction(msg, [payer, owner]))
+    assert isinstance(failed, FailedTransactionMetadata)
+    # Only the fee payer changes, since failed transactions still pay fees.
+    assert results[-1] == (failed, [payer.pubkey()])
+    assert len(dest_states) == 1
+
+    def broken_invariant(
+        _result: TransactionResult, _changed_accounts: List[Tuple[Pubkey, Account]]
+    ) -> None:
+        raise AssertionError("invariant violated")
+
+    client.on_transaction(broken_invariant)
+    client.expire_blockhash()
+    msg = Message.new_with_blockhash(
+        [token_transfer], payer.pubkey(), client.latest_blockhash()
+    )
+    with pytest.raises(AssertionError, match="invariant violated"):
+        client.send_transaction(VersionedTransaction(msg, [payer, owner]))
+    # The transaction was still processed.
+    dest_account = client.get_account(dest)
+    assert dest_account is not None
+    assert TokenAccount.from_bytes(dest_account.data).amount == 80
+
+
+def test_signature_history() -> None:
+    client = LiteSVM().with_auto_advance()
+    payer = Keypair()
+    airdrop_sig = client.airdrop(payer.pubkey(), 1_000_000_000).signature()
+    receiver = Pubkey.new_unique()
+    other = Pubkey.new_unique()
+    sigs = []
+    for i, to_pubkey in enumerate([receiver, other, receiver, receiver]):
+        ix = transfer(
+            {"from_pubkey": payer.pubkey(), "to_pubkey": to_pubkey, "lamports": 1 + i}
+        )
+        msg = Message.new_with_blockhash(
+            [ix], payer.pubkey(), client.latest_blockhash()
+        )
+        res = client.send_transaction(VersionedTransaction(msg, [payer]))
+        assert isinstance(res, TransactionMetadata)
+        sigs.append(res.signature())
+    overdraw = transfer(
+        {"from_pubkey": payer.pubkey(), "to_pubkey": receiver, "lamports": 10**18}
+    )
+    msg = Message.new_with_blockhash(
+        [overdraw], payer.pubkey(), client.latest_blockhash()
+    )
+    failed = client.send_transaction(VersionedTransaction(msg, [payer]))
+    assert isinstance(failed, FailedTransactionMetadata)
+    failed_sig = failed.meta().signature()
+    history = client.get_signatures_for_address(receiver)
+    assert [x.signature for x in history] == [failed_sig, sigs[3], sigs[2], sigs[0]]
+    assert history[0].err == failed.err()
+    assert history[1].err is None
+    assert history[1].slot < history[0].slot
+    assert history[0].confirmation_status == TransactionConfirmationStatus.Finalized
+    other_history = client.get_signatures_for_address(other)
+    assert [x.signature for x in other_history] == [sigs[1]]
+    payer_history = client.get_signatures_for_address(payer.pubkey())
+    assert [x.signature for x in payer_history][-1] == airdrop_sig
+    paged = client.get_signatures_for_address(receiver, before=sigs[3], limit=1)
+    assert [x.signature for x in paged] == [sigs[2]]
+    bounded = client.get_signatures_for_address(
+        receiver, before=failed_sig, until=sigs[0]
+    )
+    assert [x.signature for x in bounded] == [sigs[3], sigs[2]]
+    unknown = Keypair().sign_message(b"unknown")
+    statuses = client.get_signature_statuses([sigs[0], failed_sig, unknown])
+    assert statuses[0] is not None
+    assert statuses[0].slot == history[-1].slot
+    assert statuses[0].err is None
+    assert statuses[1] is not None
+    assert statuses[1].err == failed.err()
+    assert statuses[2] is None
+    # Entries evicted from the capped transaction history are no longer returned.
+    client.with_transaction_history(0)
+    assert client.get_signatures_for_address(receiver) == []
+    assert client.get_signature_statuses([sigs[0]]) == [None]
+
+
+def test_send_transactions() -> None:
+    client = LiteSVM().with_auto_advance()
+    payer = Keypair()
+    client.airdrop(payer.pubkey(), 1_000_000_000)
+    receiver = Pubkey.new_unique()
+    results = []
+    client.on_transaction(lambda result, _changed: results.append(result))
+    slot = client.get_clock().slot
+    blockhash = client.latest_blockhash()
+
+    def transfer_tx(lamports: int) -> VersionedTransaction:
+        ix = transfer(
+            {"from_pubkey": payer.pubkey(), "to_pubkey": receiver, "lamports": lamports}
+        )
+        msg = Message.new_with_blockhash([ix], payer.pubkey(), blockhash)
+        return VersionedTransaction(msg, [payer])
+
+    txs = [transfer_tx(1_000_000), transfer_tx(10**18), transfer_tx(2_000_000)]
+    payer_balance = client.get_balance(payer.pubkey())
+    rolled_back = client.send_transactions(txs, atomic=True)
+    assert len(rolled_back) == 2
+    assert isinstance(rolled_back[0], TransactionMetadata)
+    assert isinstance(rolled_back[1], FailedTransactionMetadata)
+    # Nothing was kept, not even the fees.
+    assert client.get_balance(receiver) is None
+    assert client.get_balance(payer.pubkey()) == payer_balance
+    assert client.get_transaction(txs[0].signatures[0]) is None
+    assert client.get_clock().slot == slot
+    assert results == []
+    # The same transactions can be sent again, without atomicity.
+    sent = client.send_transactions(txs)
+    assert [type(x) for x in sent] == [
+        TransactionMetadata,
+        FailedTransactionMetadata,
+        TransactionMetadata,
+    ]
+    assert results == sent
+    assert client.get_balance(receiver) == 3_000_000
+    assert client.get_balance(payer.pubkey()) == payer_balance - 3_000_000 - 3 * 5000
+    # The batch landed in a single slot.
+    assert client.get_clock().slot == slot + 1
+    statuses = client.get_signature_statuses([tx.signatures[0] for tx in txs])
+    assert {x.slot for x in statuses if x is not None} == {slot}
+    blockhash = client.latest_blockhash()
+    kept = client.send_transactions([transfer_tx(3), transfer_tx(4)], atomic=True)
+    assert all(isinstance(x, TransactionMetadata) for x in kept)
+    assert client.get_balance(receiver) == 3_000_007
+
+
+@pytest.mark.parametrize(
+    "token_program_id",
+    [
+        TOKEN_PROGRAM_ID,
+        Pubkey.from_string("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"),
+    ],
+)
+def test_token_cheat_codes(token_program_id: Pubkey) -> None:
+    client = LiteSVM()
+    authority, alice, bob = Keypair(), Keypair(), Keypair()
+    client.airdrop(alice.pubkey(), 1_000_000_000)
+    mint = client.create_mint(authority.pubkey(), 6, token_program_id=token_program_id)
+    mint_account = client.get_account(mint)
+    assert mint_account is not None
+    assert mint_account.owner == token_program_id
+    assert mint_account.lamports == client.minimum_balance_for_rent_exemption(82)
+    alice_ata = client.create_ata(alice.pubkey(), mint)
+    assert alice_ata == get_associated_token_address(
+        alice.pubkey(), mint, token_program_id
+    )
+    assert client.create_ata(alice.pubkey(), mint) == alice_ata
+    bob_ata = client.create_ata(bob.pubkey(), mint)
+    client.mint_to(mint, alice_ata, 1_000)
+    client.set_token_balance(bob_ata, 50)
+
+    def supply() -> int:
+        account = client.get_account(mint)
+        assert account is not None
+        return Mint.from_bytes(account.data[:82]).supply
+
+    def balance(address: Pubkey) -> int:
+        account = client.get_account(address)
+        assert account is not None
+        return TokenAccount.from_bytes(account.data[:165]).amount
+
+    assert (balance(alice_ata), balance(bob_ata), supply()) == (1_000, 50, 1_050)
+    client.set_token_balance(alice_ata, 400)
+    assert supply() == 450
+    # the accounts are usable by the token program
+    token_transfer = Instruction(
+        token_program_id,
+        bytes([3]) + (100).to_bytes(8, "little"),
+        [
+            AccountMeta(alice_ata, is_signer=False, is_writable=True),
+            AccountMeta(bob_ata, is_signer=False, is_writable=True),
+            AccountMeta(alice.pubkey(), is_signer=True, is_writable=False),
+        ],
+    )
+    msg = Message.new_with_blockhash(
+        [token_transfer], alice.pubkey(), client.latest_blockhash()
+    )
+    res = client.send_transaction(VersionedTransaction(msg, [alice]))
+    assert isinstance(res, TransactionMetadata), res
+    assert (balance(alice_ata), balance(bob_ata)) == (300, 150)
+    other_mint = client.create_mint(authority.pubkey(), 0)
+    with pytest.raises(ValueError):
+        client.mint_to(other_mint, alice_ata, 1)
+
+
+def test_get_program_accounts() -> None:
+    client = LiteSVM()
+    authority = Keypair()
+    mint = client.create_mint(authority.pubkey(), 0)
+    owners = [Pubkey.new_unique() for _ in range(3)]
+    atas = [client.create_ata(owner, mint) for owner in owners]
+    all_accounts = list(client.accounts())
+    addresses = [address for address, _ in all_accounts]
+    assert addresses == sorted(addresses)
+    assert {mint, *atas} <= set(addresses)
+    everything = client.get_program_accounts(TOKEN_PROGRAM_ID)
+    assert {address for address, _ in everything} == {mint, *atas}
+    token_accounts = client.get_program_accounts(
+        TOKEN_PROGRAM_ID, [RpcFilterTypeFieldless.TokenAccountState]
+    )
+    assert {address for address, _ in token_accounts} == set(atas)
+    by_owner = client.get_program_accounts(
+        TOKEN_PROGRAM_ID, [165, Memcmp(offset=32, bytes_=bytes(owners[1]))]
+    )
+    assert [address for address, _ in by_owner] == [atas[1]]
+    mints = client.get_program_accounts(TOKEN_PROGRAM_ID, [82])
+    assert [address for address, _ in mints] == [mint]
+    assert client.get_program_accounts(Pubkey.new_unique()) == []
+
+
+def test_mock_program() -> None:
+    client = LiteSVM()
+    oracle_id = Pubkey.new_unique()
+    price_account = Pubkey.new_unique()
+    client.set_account(
+        price_account, Account(lamports=1_000_000, data=bytes(8), owner=oracle_id)
+    )
+    calls = []
+
+    def oracle(
+        program_id: Pubkey, accounts: List[AccountInfo], data: bytes
+    ) -> Union[bytes, InstructionErrorType]:
+        calls.append((program_id, [acc.key for acc in accounts], data))
+        if not data:
+            return InstructionErrorCustom(42)
+        if data == b"raise":
+            raise ValueError("oracle is down")
+        price = accounts[0]
+        assert price.is_writable
+        assert not price.is_signer
+        assert price.owner == oracle_id
+        price.data = data.ljust(8, b"\0")
+        return price.data
+
+    client.add_mock_program(oracle_id, oracle)
+    payer = Keypair()
+    client.airdrop(payer.pubkey(), 1_000_000_000)
+
+    def call(data: bytes) -> TransactionResult:
+        accounts = [AccountMeta(price_account, is_signer=False, is_writable=True)]
+        ix = Instruction(oracle_id, data, accounts)
+        client.expire_blockhash()
+        msg = Message.new_with_blockhash(
+            [ix], payer.pubkey(), client.latest_blockhash()
+        )
+        return client.send_transaction(VersionedTransaction(msg, [payer]))
+
+    price = (1234).to_bytes(8, "little")
+    res = call(price)
+    assert isinstance(res, TransactionMetadata)
+    assert calls == [(oracle_id, [price_account], price)]
+    assert res.return_data() == TransactionReturnData(oracle_id, price)
+    price_state = client.get_account(price_account)
+    assert price_state is not None
+    assert price_state.data == price
+    failed = call(b"")
+    assert isinstance(failed, FailedTransactionMetadata)
+    assert failed.err() == TransactionErrorInstructionError(
+        0, InstructionErrorCustom(42)
+    )
+    failed = call(b"raise")
+    assert isinstance(failed, FailedTransactionMetadata)
+    assert failed.err() == TransactionErrorInstructionError(
+        0, InstructionErrorFieldless.ProgramFailedToComplete
+    )
+    assert any("oracle is down" in log for log in failed.meta().logs())
+    # The runtime still checks what the mock changes.
+    foreign_account = Pubkey.new_unique()
+    client.set_account(
+        foreign_account,
+        Account(lamports=1_000_000, data=bytes(8), owner=Pubkey.new_unique()),
+    )
+
+    def thief(program_id: Pubkey, accounts: list, data: bytes) -> None:
+        accounts[0].lamports = 0
+
+    thief_id = Pubkey.new_unique()
+    client.add_mock_program(thief_id, thief)
+    ix = Instruction(
+        thief_id, b"", [AccountMeta(foreign_account, is_signer=False, is_writable=True)]
+    )
+    msg = Message.new_with_blockhash([ix], payer.pubkey(), client.latest_blockhash())
+    failed = client.send_transaction(VersionedTransaction(msg, [payer]))
+    assert isinstance(failed, FailedTransactionMetadata)
+    assert failed.err() == TransactionErrorInstructionError(
+        0, InstructionErrorFieldless.ExternalAccountLamportSpend
+    )
+    assert client.get_balance(foreign_account) == 1_000_000
+
+
 def test_missing_program() -> None:
     program_id = Pubkey.new_unique()
     client = LiteSVM()
@@ -265,3 +1169,446 @@ def test_add_program_via_set_account() -> None:
     greeted_account_after = client.get_account(greeted_pubkey)
     assert greeted_account_after is not None
     assert greeted_account_after.data == bytes([1, 0, 0, 0])
+
+
+def test_feature_set() -> None:
+    client = LiteSVM(FeatureSet.all_enabled())
+    payer = Keypair()
+    airdrop_res = client.airdrop(payer.pubkey(), 1_000_000_000)
+    assert isinstance(airdrop_res, TransactionMetadata)
+    compute_budget = ComputeBudget(True)
+    assert compute_budget.max_instruction_stack_depth == 9
+    client = (
+        LiteSVM()
+        .with_compute_budget(compute_budget)
+        .with_feature_set(FeatureSet.default())
+    )
+    budget_after = client.get_compute_budget()
+    assert budget_after is not None
+    assert budget_after.max_instruction_stack_depth == 5
+    airdrop_res = client.airdrop(payer.pubkey(), 1_000_000_000)
+    assert isinstance(airdrop_res, TransactionMetadata)
+
+
+def test_feature_set_downgrade() -> None:
+    loader_v4 = Pubkey.from_string("LoaderV411111111111111111111111111111111111")
+    secp256r1 = Pubkey.from_string("Secp256r1SigVerify1111111111111111111111111")
+    client = LiteSVM()
+    assert client.get_account(loader_v4) is not None
+    assert client.get_account(secp256r1) is not None
+    client.with_feature_set(FeatureSet.default())
+    assert client.get_account(loader_v4) is None
+    assert client.get_account(secp256r1) is None
+    payer = Keypair()
+    client.airdrop(payer.pubkey(), 1_000_000_000)
+    ix = Instruction(loader_v4, b"", [])
+    msg = Message.new_with_blockhash([ix], payer.pubkey(), client.latest_blockhash())
+    tx = VersionedTransaction(msg, [payer])
+    assert isinstance(client.simulate_transaction(tx), FailedTransactionMetadata)
+    res = client.send_transaction(tx)
+    assert isinstance(res, FailedTransactionMetadata)
+    assert res.err() == TransactionErrorFieldless.InvalidProgramForExecution
+    assert client.get_account(loader_v4) is None
+    client.with_feature_set(FeatureSet.all_enabled())
+    assert client.get_account(loader_v4) is not None
+
+
+def test_snapshot_restore() -> None:
+    client = LiteSVM()
+    payer = Keypair()
+    receiver = Pubkey.new_unique()
+    client.airdrop(payer.pubkey(), 1_000_000_000)
+    snapshot = client.snapshot()
+    blockhash = client.latest_blockhash()
+    ix = transfer(
+        {"from_pubkey": payer.pubkey(), "to_pubkey": receiver, "lamports": 1_000_000}
+    )
+    msg = Message.new_with_blockhash([ix], payer.pubkey(), blockhash)
+    tx = VersionedTransaction(msg, [payer])
+    res = client.send_transaction(tx)
+    assert isinstance(res, TransactionMetadata)
+    client.expire_blockhash()
+    client.warp_to_slot(100)
+    assert client.get_balance(receiver) == 1_000_000
+    for _ in range(2):
+        client.restore(snapshot)
+        assert client.get_balance(receiver) is None
+        assert client.get_balance(payer.pubkey()) == 1_000_000_000
+        assert client.latest_blockhash() == blockhash
+        assert client.get_clock().slot == 0
+        assert client.get_transaction(tx.signatures[0]) is None
+        assert isinstance(client.send_transaction(tx), TransactionMetadata)
+
+
+def test_fork() -> None:
+    client = LiteSVM(seed=1).with_account_diffs()
+    payer = Keypair()
+    client.airdrop(payer.pubkey(), 1_000_000_000)
+    seen: List[TransactionResult] = []
+    client.on_transaction(lambda res, _changed: seen.append(res))
+    fork = client.fork()
+    receiver = Pubkey.new_unique()
+    ix = transfer(
+        {"from_pubkey": payer.pubkey(), "to_pubkey": receiver, "lamports": 1_000_000}
+    )
+    msg = Message.new_with_blockhash([ix], payer.pubkey(), fork.latest_blockhash())
+    res = fork.send_transaction(VersionedTransaction(msg, [payer]))
+    assert isinstance(res, TransactionMetadata)
+    assert res.account_diffs() is not None
+    assert fork.get_balance(receiver) == 1_000_000
+    assert client.get_balance(receiver) is None
+    # Callbacks are not copied, but the seeded keys are.
+    assert seen == []
+    assert fork.new_keypair() == client.new_keypair()
+    assert fork.get_signatures_for_address(payer.pubkey())[1:] == (
+        client.get_signatures_for_address(payer.pubkey())
+    )
+
+
+def test_send_transactions_parallel() -> None:
+    base, program_id, greeted_pubkey = helloworld_program()
+    payer = Keypair()
+    base.airdrop(payer.pubkey(), 1_000_000_000)
+    ix = Instruction(
+        program_id,
+        bytes([0]),
+        [AccountMeta(greeted_pubkey, is_signer=False, is_writable=True)],
+    )
+    msg = Message.new_with_blockhash([ix], payer.pubkey(), base.latest_blockhash())
+    tx = VersionedTransaction(msg, [payer])
+    compute_unit_limits = [10, 1_000, 10_000, 200_000, 10, 1_000, 10_000, 200_000]
+    forks = []
+    for limit in compute_unit_limits:
+        budget = ComputeBudget(False)
+        budget.compute_unit_limit = limit
+        forks.append(base.fork().with_compute_budget(budget))
+    seen: List[TransactionResult] = []
+    forks[3].on_transaction(lambda res, _changed: seen.append(res))
+    results = LiteSVM.send_transactions_parallel(
+        [(fork, [tx]) for fork in forks], max_workers=3
+    )
+    assert len(results) == len(forks)
+    for fork, limit, [res] in zip(forks, compute_unit_limits, results):
+        greeted = fork.get_account(greeted_pubkey)
+        assert greeted is not None
+        if limit >= 10_000:
+            assert isinstance(res, TransactionMetadata)
+            assert greeted.data == bytes([1, 0, 0, 0])
+        else:
+            assert isinstance(res, FailedTransactionMetadata)
+            assert greeted.data == bytes(4)
+    assert seen == [results[3][0]]
+    base_greeted = base.get_account(greeted_pubkey)
+    assert base_greeted is not None
+    assert base_greeted.data == bytes(4)
+    with pytest.raises(ValueError, match="its own LiteSVM instance"):
+        LiteSVM.send_transactions_parallel([(forks[0], []), (forks[0], [])])
+
+
+def test_save_load(tmp_path: Path) -> None:
+    client, program_id, greeted_pubkey = helloworld_program()
+    client.set_clock(
+        Clock(
+            slot=1000,
+            epoch_start_timestamp=1,
+            epoch=100,
+            leader_schedule_epoch=3,
+            unix_timestamp=4,
+        )
+    )
+    path = tmp_path / "world.bin"
+    client.save(path)
+    loaded = LiteSVM.load(path)
+    assert loaded.get_clock().slot == 1000
+    assert loaded.latest_blockhash() == client.latest_blockhash()
+    assert len(list(loaded.accounts())) == len(list(client.accounts()))
+    loaded.save(path)
+    assert len(list(LiteSVM.load(path).accounts())) == len(list(client.accounts()))
+    assert loaded.get_account(greeted_pubkey) == client.get_account(greeted_pubkey)
+    assert loaded.get_account(program_id) == client.get_account(program_id)
+    ix = Instruction(
+        program_id,
+        bytes([0]),
+        [AccountMeta(greeted_pubkey, is_signer=False, is_writable=True)],
+    )
+    payer = Keypair()
+    loaded.airdrop(payer.pubkey(), 1_000_000_000)
+    msg = Message.new_with_blockhash([ix], payer.pubkey(), loaded.latest_blockhash())
+    res = loaded.send_transaction(VersionedTransaction(msg, [payer]))
+    assert isinstance(res, TransactionMetadata)
+    greeted_account_after = loaded.get_account(greeted_pubkey)
+    assert greeted_account_after is not None
+    assert greeted_account_after.data == bytes([1, 0, 0, 0])
+    seeded = LiteSVM(seed=7)
+    seeded.new_keypair()
+    seeded.expire_blockhash()
+    seeded.save(path)
+    loaded_seeded = LiteSVM.load(path)
+    assert loaded_seeded.latest_blockhash() == seeded.latest_blockhash()
+    assert loaded_seeded.new_keypair() == seeded.new_keypair()
+    # Only the saved blockhash is accepted, not LiteSVM's genesis blockhash.
+    loaded_seeded.airdrop(payer.pubkey(), 1_000_000_000)
+    transfer_ix = transfer(
+        {"from_pubkey": payer.pubkey(), "to_pubkey": Pubkey.new_unique(), "lamports": 1}
+    )
+    for blockhash, ok in [
+        (LiteSVM().latest_blockhash(), False),
+        (seeded.latest_blockhash(), True),
+    ]:
+        msg = Message.new_with_blockhash([transfer_ix], payer.pubkey(), blockhash)
+        res = loaded_seeded.send_transaction(VersionedTransaction(msg, [payer]))
+        assert isinstance(res, TransactionMetadata) == ok
+
+
+def test_durable_nonce() -> None:
+    client = LiteSVM(seed=3)
+    payer = client.new_keypair()
+    nonce = client.new_keypair()
+    client.airdrop(payer.pubkey(), 1_000_000_000)
+    create_ixs = create_nonce_account(
+        payer.pubkey(), nonce.pubkey(), payer.pubkey(), 10_000_000
+    )
+    msg = Message.new_with_blockhash(
+        list(create_ixs), payer.pubkey(), client.latest_blockhash()
+    )
+    res = client.send_transaction(VersionedTransaction(msg, [payer, nonce]))
+    assert isinstance(res, TransactionMetadata)
+    client.expire_blockhash()
+
+    def durable_nonce() -> Hash:
+        nonce_account = client.get_account(nonce.pubkey())
+        assert nonce_account is not None
+        # The durable nonce follows the version, state and authority fields.
+        return Hash(nonce_account.data[40:72])
+
+    def nonced_tx(blockhash: Hash, lamports: int) -> VersionedTransaction:
+        params = AdvanceNonceAccountParams(
+            nonce_pubkey=nonce.pubkey(), authorized_pubkey=payer.pubkey()
+        )
+        ixs = [
+            advance_nonce_account(params),
+            transfer(
+                {
+                    "from_pubkey": payer.pubkey(),
+                    "to_pubkey": Pubkey.new_unique(),
+                    "lamports": lamports,
+                }
+            ),
+        ]
+        msg = Message.new_with_blockhash(ixs, payer.pubkey(), blockhash)
+        return VersionedTransaction(msg, [payer])
+
+    first_nonce = durable_nonce()
+    assert first_nonce != client.latest_blockhash()
+    tx = nonced_tx(first_nonce, 1_000_000)
+    assert isinstance(client.simulate_transaction(tx), SimulatedTransactionInfo)
+    assert isinstance(client.send_transaction(tx), TransactionMetadata)
+    assert durable_nonce() != first_nonce
+    # The nonce account has moved on, so its old value is no longer accepted.
+    stale = client.send_transaction(nonced_tx(first_nonce, 2_000_000))
+    assert isinstance(stale, FailedTransactionMetadata)
+    assert stale.err() == TransactionErrorFieldless.BlockhashNotFound
+    res = client.send_transaction(nonced_tx(durable_nonce(), 2_000_000))
+    assert isinstance(res, TransactionMetadata)
+
+
+def test_account_json_dumps(tmp_path: Path) -> None:
+    address = Pubkey.from_string("4ivLcnNLhe4cKdpV9b4jyEmxgbYWFgktHcTyyBvYavsD")
+    cli_dump = (
+        '{"pubkey":"4ivLcnNLhe4cKdpV9b4jyEmxgbYWFgktHcTyyBvYavsD",'
+        '"account":{"lamports":1000000,"data":["AQIDBA==","base64"],'
+        '"owner":"11111111111111111111111111111111","executable":false,'
+        '"rentEpoch":18446744073709551615,"space":4}}'
+    )
+    path = tmp_path / "account.json"
+    path.write_text(cli_dump)
+    client = LiteSVM()
+    assert client.load_account_from_json(path) == address
+    expected = Account(
+        lamports=1_000_000,
+        data=bytes([1, 2, 3, 4]),
+        owner=Pubkey.from_string("11111111111111111111111111111111"),
+        rent_epoch=2**64 - 1,
+    )
+    assert client.get_account(address) == expected
+    other = Pubkey.new_unique()
+    client.set_account(other, Account(lamports=5, data=b"", owner=Pubkey.default()))
+    dump_dir = tmp_path / "dumps"
+    client.save_accounts_to_dir([address, other], dump_dir)
+    loaded = LiteSVM()
+    assert sorted(loaded.load_accounts_from_dir(dump_dir)) == sorted([address, other])
+    assert loaded.get_account(address) == expected
+    assert loaded.get_balance(other) == 5
+
+
+def test_add_accounts_from_rpc_response() -> None:
+    loader = Pubkey.from_string("BPFLoaderUpgradeab1e11111111111111111111111")
+    program_id = Pubkey.new_unique()
+    programdata_address = Pubkey.new_unique()
+    greeted_pubkey = Pubkey.new_unique()
+    elf = Path("tests/fixtures/helloworld.so").read_bytes()
+    program = Account(
+        lamports=1_000_000_000,
+        data=bytes([2, 0, 0, 0]) + bytes(programdata_address),
+        owner=loader,
+        executable=True,
+    )
+    programdata = Account(
+        lamports=1_000_000_000,
+        data=bytes([3, 0, 0, 0]) + bytes(8) + bytes([0]) + bytes(32) + elf,
+        owner=loader,
+    )
+    greeted = Account(lamports=1_000_000_000, data=bytes(4), owner=program_id)
+    client = LiteSVM()
+    with pytest.raises(ValueError):
+        client.add_accounts_from_rpc_response(
+            GetProgramAccountsResp([RpcKeyedAccount(program_id, program)])
+        )
+    resp = GetMultipleAccountsResp(
+        [program, None, greeted, programdata], RpcResponseContext(slot=1)
+    )
+    addresses = [program_id, Pubkey.new_unique(), greeted_pubkey, programdata_address]
+    installed = client.add_accounts_from_rpc_response(resp, addresses)
+    assert sorted(installed) == sorted(
+        [program_id, greeted_pubkey, programdata_address]
+    )
+    ix = Instruction(
+        program_id,
+        bytes([0]),
+        [AccountMeta(greeted_pubkey, is_signer=False, is_writable=True)],
+    )
+    payer = Keypair()
+    client.airdrop(payer.pubkey(), 1_000_000_000)
+    msg = Message.new_with_blockhash([ix], payer.pubkey(), client.latest_blockhash())
+    res = client.send_transaction(VersionedTransaction(msg, [payer]))
+    assert isinstance(res, TransactionMetadata)
+    greeted_account_after = client.get_account(greeted_pubkey)
+    assert greeted_account_after is not None
+    assert greeted_account_after.data == bytes([1, 0, 0, 0])
+
+
+def test_upgradeable_program() -> None:
+    program_id = Pubkey.new_unique()
+    greeted_pubkey = Pubkey.new_unique()
+    authority = Keypair()
+    elf = Path("tests/fixtures/helloworld.so").read_bytes()
+    client = LiteSVM()
+    client.add_upgradeable_program(program_id, elf, authority.pubkey())
+    program = client.get_account(program_id)
+    assert program is not None
+    assert program.executable
+    programdata_address = Pubkey.find_program_address(
+        [bytes(program_id)], program.owner
+    )[0]
+    assert bytes(program.data[4:]) == bytes(programdata_address)
+    programdata = client.get_account(programdata_address)
+    assert programdata is not None
+    assert programdata.data[13:45] == bytes(authority.pubkey())
+    assert programdata.data[45:] == elf
+    client.set_account(
+        greeted_pubkey, Account(lamports=5, data=bytes(4), owner=program_id)
+    )
+    ix = Instruction(
+        program_id,
+        bytes([0]),
+        [AccountMeta(greeted_pubkey, is_signer=False, is_writable=True)],
+    )
+    client.airdrop(authority.pubkey(), 10_000_000_000)
+    msg = Message.new_with_blockhash(
+        [ix], authority.pubkey(), client.latest_blockhash()
+    )
+    res = client.send_transaction(VersionedTransaction(msg, [authority]))
+    assert isinstance(res, TransactionMetadata)
+    impostor = Keypair()
+    client.airdrop(impostor.pubkey(), 10_000_000_000)
+    client.warp_to_slot(1)
+    loader_accounts = client.get_program_accounts(program.owner)
+    bad_upgrade = client.upgrade_program(program_id, elf, impostor)
+    assert isinstance(bad_upgrade, FailedTransactionMetadata)
+    assert client.get_transaction(bad_upgrade.meta().signature()) is not None
+    bigger_elf = elf + bytes(1000)
+    bad_extend = client.upgrade_program(program_id, bigger_elf, impostor)
+    assert isinstance(bad_extend, FailedTransactionMetadata)
+    # The failed attempts don't leave their buffer accounts behind.
+    assert client.get_program_accounts(program.owner) == loader_accounts
+    good_upgrade = client.upgrade_program(program_id, bigger_elf, authority)
+    assert isinstance(good_upgrade, TransactionMetadata)
+    programdata_after = client.get_account(programdata_address)
+    assert programdata_after is not None
+    assert programdata_after.data[45:] == bigger_elf
+    assert client.get_clock().slot == 2
+
+
+def send_via_lookup_table(
+    client: LiteSVM, payer: Keypair, table_address: Pubkey, to: Pubkey, lamports: int
+) -> TransactionResult:
+    table_account = client.get_account(table_address)
+    assert table_account is not None
+    addresses = AddressLookupTable.deserialize(table_account.data).addresses
+    ix = transfer(
+        {"from_pubkey": payer.pubkey(), "to_pubkey": to, "lamports": lamports}
+    )
+    msg = MessageV0.try_compile(
+        payer.pubkey(),
+        [ix],
+        [AddressLookupTableAccount(table_address, addresses)],
+        client.latest_blockhash(),
+    )
+    return client.send_transaction(VersionedTransaction(msg, [payer]))
+
+
+def send_legacy(client: LiteSVM, payer: Keypair, ix: Instruction) -> TransactionResult:
+    client.expire_blockhash()
+    msg = Message.new_with_blockhash([ix], payer.pubkey(), client.latest_blockhash())
+    return client.send_transaction(VersionedTransaction(msg, [payer]))
+
+
+def test_lookup_table_lifecycle() -> None:
+    client = LiteSVM()
+    payer = Keypair()
+    client.airdrop(payer.pubkey(), 10_000_000_000)
+    receivers = [Pubkey.new_unique() for _ in range(25)]
+    table = client.create_lookup_table(payer, receivers)
+    table_account = client.get_account(table)
+    assert table_account is not None
+    assert table_account.owner == ADDRESS_LOOKUP_TABLE_ID
+    assert AddressLookupTable.deserialize(table_account.data).addresses == receivers
+    # One create transaction and two extend transactions.
+    assert len(client.get_signatures_for_address(table)) == 3
+    res = send_via_lookup_table(client, payer, table, receivers[24], 1_000_000)
+    assert isinstance(res, TransactionMetadata)
+    assert client.get_balance(receivers[24]) == 1_000_000
+    # Addresses can't be loaded in the slot they were added in.
+    extra = Pubkey.new_unique()
+    extend_ix = extend_lookup_table(
+        {
+            "payer_address": payer.pubkey(),
+            "lookup_table_address": table,
+            "authority_address": payer.pubkey(),
+            "new_addresses": [extra],
+        }
+    )
+    assert isinstance(send_legacy(client, payer, extend_ix), TransactionMetadata)
+    res = send_via_lookup_table(client, payer, table, extra, 1_000_000)
+    assert isinstance(res, FailedTransactionMetadata)
+    assert res.err() == TransactionErrorFieldless.InvalidAddressLookupTableIndex
+    client.warp_to_slot(client.get_clock().slot + 1)
+    res = send_via_lookup_table(client, payer, table, extra, 1_000_000)
+    assert isinstance(res, TransactionMetadata)
+    # A deactivated table can only be closed once its deactivation slot
+    # has left SlotHashes, which takes 512 more slots.
+    authority = {"lookup_table_address": table, "authority_address": payer.pubkey()}
+    deactivate_ix = deactivate_lookup_table(authority)
+    assert isinstance(send_legacy(client, payer, deactivate_ix), TransactionMetadata)
+    close_ix = close_lookup_table({**authority, "recipient_address": payer.pubkey()})
+    for _ in range(512):
+        client.warp_to_slot(client.get_clock().slot + 1)
+    res = send_via_lookup_table(client, payer, table, extra, 2_000_000)
+    assert isinstance(res, TransactionMetadata)
+    assert isinstance(send_legacy(client, payer, close_ix), FailedTransactionMetadata)
+    client.warp_to_slot(client.get_clock().slot + 1)
+    res = send_via_lookup_table(client, payer, table, extra, 3_000_000)
+    assert isinstance(res, FailedTransactionMetadata)
+    assert res.err() == TransactionErrorFieldless.AddressLookupTableNotFound
+    assert isinstance(send_legacy(client, payer, close_ix), TransactionMetadata)
+    assert client.get_account(table) is None
@@ -0,0 +1,343 @@
+import base64
+import json
+import os
+import socket
+import struct
+from typing import Any, Tuple
+from urllib.parse import urlparse
+from urllib.request import Request, urlopen
+
+from solders.compute_budget import set_compute_unit_limit, set_compute_unit_price
+from solders.keypair import Keypair
+from solders.litesvm import LiteSVM
+from solders.litesvm_server import LiteSVMServer
+from solders.message import Message
+from solders.pubkey import Pubkey
+from solders.rpc.config import RpcTransactionLogsFilterMentions
+from solders.rpc.requests import (
+    AccountSubscribe,
+    GetAccountInfo,
+    GetBalance,
+    GetFeeForMessage,
+    GetGenesisHash,
+    GetLatestBlockhash,
+    GetProgramAccounts,
+    GetSignatureStatuses,
+    GetTransaction,
+    LogsSubscribe,
+    ProgramSubscribe,
+    ProgramUnsubscribe,
+    SendVersionedTransaction,
+    SignatureSubscribe,
+    SimulateVersionedTransaction,
+    SlotSubscribe,
+    batch_to_json,
+)
+from solders.rpc.responses import (
+    GetAccountInfoResp,
+    GetBalanceResp,
+    GetFeeForMessageResp,
+    GetGenesisHashResp,
+    GetLatestBlockhashResp,
+    GetProgramAccountsResp,
+    GetSignatureStatusesResp,
+    GetTransactionResp,
+    LogsNotification,
+    ProgramNotification,
+    SendTransactionResp,
+    SignatureNotification,
+    SimulateTransactionResp,
+    parse_websocket_message,
+)
+from solders.system_program import ID as SYSTEM_PROGRAM_ID
+from solders.system_program import transfer
+from solders.transaction import VersionedTransaction
+from solders.transaction_status import TransactionConfirmationStatus
+
+
+def post(server: LiteSVMServer, body: str) -> str:
+    req = Request(
+        server.url, body.encode(), headers={"Content-Type": "application/json"}
+    )
+    with urlopen(req) as resp:
+        return resp.read().decode()
+
+
+class WebSocketClient:
+    def __init__(self, url: str) -> None:
+        parsed = urlparse(url)
+        self.sock = socket.create_connection((parsed.hostname, parsed.port), timeout=5)
+        key = base64.b64encode(os.urandom(16)).decode()
+        self.sock.sendall(
+            (
+                f"GET / HTTP/1.1\r\nHost: {parsed.netloc}\r\nUpgrade: websocket\r\n"
+                f"Connection: Upgrade\r\nSec-WebSocket-Key: {key}\r\n"
+                "Sec-WebSocket-Version: 13\r\n\r\n"
+            ).encode()
+        )
+        self.file = self.sock.makefile("rb")
+        assert b" 101 " in self.file.readline()
+        while self.file.readline() != b"\r\n":
+            pass
+
+    def send(self, message: str) -> None:
+        payload = message.encode()
+        mask = os.urandom(4)
+        masked = bytes(b ^ mask[i % 4] for i, b in enumerate(payload))
+        if len(payload) < 126:
+            header = struct.pack("!BB", 0x81, 0x80 | len(payload))
+        else:
+            header = struct.pack("!BBH", 0x81, 0x80 | 126, len(payload))
+        self.sock.sendall(header + mask + masked)
+
+    def recv(self) -> Any:
+        _, length = self.file.read(2)
+        if length == 126:
+            (length,) = struct.unpack("!H", self.file.read(2))
+        elif length == 127:
+            (length,) = struct.unpack("!Q", self.file.read(8))
+        return json.loads(self.file.read(length))
+
+    def close(self) -> None:
+        self.sock.close()
+
+
+def transfer_tx(
+    server: LiteSVMServer, lamports: int
+) -> Tuple[Keypair, Pubkey, VersionedTransaction]:
+    payer = Keypair()
+    receiver = Pubkey.new_unique()
+    server.svm.airdrop(payer.pubkey(), 1_000_000_000)
+    ix = transfer(
+        {"from_pubkey": payer.pubkey(), "to_pubkey": receiver, "lamports": lamports}
+    )
+    blockhash = server.svm.latest_blockhash()
+    msg = Message.new_with_blockhash([ix], payer.pubkey(), blockhash)
+    return payer, receiver, VersionedTransaction(msg, [payer])
+
+
+def test_get_balance_and_account_info() -> None:
+    with LiteSVMServer(LiteSVM()) as server:
+        address = Pubkey.new_unique()
+        server.svm.airdrop(address, 1_000)
+        balance_raw = post(server, GetBalance(address, id=7).to_json())
+        balance = GetBalanceResp.from_json(balance_raw)
+        assert isinstance(balance, GetBalanceResp)
+        assert balance.value == 1_000
+        info_raw = post(server, GetAccountInfo(address).to_json())
+        info = GetAccountInfoResp.from_json(info_raw)
+        assert isinstance(info, GetAccountInfoResp)
+        assert info.value == server.svm.get_account(address)
+        missing = post(server, GetAccountInfo(Pubkey.new_unique()).to_json())
+        assert GetAccountInfoResp.from_json(missing).value is None
+
+
+def test_latest_blockhash() -> None:
+    with LiteSVMServer(LiteSVM()) as server:
+        raw = post(server, GetLatestBlockhash().to_json())
+        resp = GetLatestBlockhashResp.from_json(raw)
+        assert isinstance(resp, GetLatestBlockhashResp)
+        assert resp.value.blockhash == server.svm.latest_blockhash()
+
+
+def test_genesis_hash() -> None:
+    svm = LiteSVM(seed=5)
+    genesis_hash = svm.latest_blockhash()
+    with LiteSVMServer(svm) as server:
+        svm.expire_blockhash()
+        raw = post(server, GetGenesisHash().to_json())
+        resp = GetGenesisHashResp.from_json(raw)
+        assert isinstance(resp, GetGenesisHashResp)
+        assert resp.value == genesis_hash
+        assert resp.value != LiteSVM(seed=6).latest_blockhash()
+
+
+def test_send_and_query_transaction() -> None:
+    with LiteSVMServer(LiteSVM()) as server:
+        payer, receiver, tx = transfer_tx(server, 1_000_000)
+        sim_raw = post(server, SimulateVersionedTransaction(tx).to_json())
+        sim = SimulateTransactionResp.from_json(sim_raw)
+        assert isinstance(sim, SimulateTransactionResp)
+        assert sim.value.err is None
+        assert sim.value.fee == 5000
+        assert server.svm.get_balance(receiver) is None
+        sent = SendTransactionResp.from_json(
+            post(server, SendVersionedTransaction(tx).to_json())
+        )
+        assert isinstance(sent, SendTransactionResp)
+        assert sent.value == tx.signatures[0]
+        assert server.svm.get_balance(receiver) == 1_000_000
+        statuses_raw = post(server, GetSignatureStatuses([sent.value]).to_json())
+        statuses = GetSignatureStatusesResp.from_json(statuses_raw)
+        assert isinstance(statuses, GetSignatureStatusesResp)
+        status = statuses.value[0]
+        assert status is not None
+        assert status.err is None
+        assert status.confirmation_status == TransactionConfirmationStatus.Finalized
+        got_raw = post(server, GetTransaction(sent.value).to_json())
+        got = GetTransactionResp.from_json(got_raw)
+        assert isinstance(got, GetTransactionResp)
+        assert got.value is not None
+        meta = got.value.transaction.meta
+        assert meta is not None
+        assert meta.err is None
+        assert meta.fee == 5000
+        assert meta.pre_balances[0] - meta.post_balances[0] == 1_005_000
+        assert meta.post_balances[1] - meta.pre_balances[1] == 1_000_000
+        assert meta.log_messages is not None
+
+
+def test_send_failing_transaction() -> None:
+    with LiteSVMServer(LiteSVM()) as server:
+        payer, _, tx = transfer_tx(server, 10_000_000_000)
+        resp = json.loads(post(server, SendVersionedTransaction(tx).to_json()))
+        assert resp["error"]["code"] == -32002
+        assert resp["error"]["data"]["logs"]
+        status = post(server, GetSignatureStatuses([tx.signatures[0]]).to_json())
+        assert GetSignatureStatusesResp.from_json(status).value == [None]
+        # The failed preflight doesn't send the transaction, so no fee is charged.
+        assert server.svm.get_balance(payer.pubkey()) == 1_000_000_000
+        assert server.svm.get_transaction(tx.signatures[0]) is None
+
+
+def test_get_fee_for_message() -> None:
+    with LiteSVMServer(LiteSVM()) as server:
+        payer = Pubkey.new_unique()
+        ixs = [
+            set_compute_unit_limit(100_000),
+            set_compute_unit_price(1_000_000),
+            transfer({"from_pubkey": payer, "to_pubkey": payer, "lamports": 1}),
+        ]
+        msg = Message.new_with_blockhash(ixs, payer, server.svm.latest_blockhash())
+        raw = post(server, GetFeeForMessage(msg).to_json())
+        resp = GetFeeForMessageResp.from_json(raw)
+        assert isinstance(resp, GetFeeForMessageResp)
+        # The base fee plus 100_000 units at 1 lamport each.
+        assert resp.value == 105_000
+
+
+def test_get_program_accounts() -> None:
+    with LiteSVMServer(LiteSVM()) as server:
+        address = Pubkey.new_unique()
+        server.svm.airdrop(address, 1_000)
+        raw = post(server, GetProgramAccounts(SYSTEM_PROGRAM_ID).to_json())
+        resp = GetProgramAccountsResp.from_json(raw)
+        assert isinstance(resp, GetProgramAccountsResp)
+        assert address in [keyed.pubkey for keyed in resp.value]
+
+
+def test_raw_requests() -> None:
+    with LiteSVMServer(LiteSVM()) as server:
+        body = '{"jsonrpc":"2.0","id":"abc","method":"getSlot","params":[]}'
+        expected = {"jsonrpc": "2.0", "result": 0, "id": "abc"}
+        assert json.loads(post(server, body)) == expected
+        unknown = json.loads(post(server, '{"jsonrpc":"2.0","id":1,"method":"nope"}'))
+        assert unknown["error"]["code"] == -32601
+        assert unknown["id"] == 1
+        bad_params = '{"jsonrpc":"2.0","id":1,"method":"getBalance","params":["x"]}'
+        assert json.loads(post(server, bad_params))["error"]["code"] == -32602
+        assert json.loads(post(server, "{"))["error"]["code"] == -32700
+
+
+def test_batch() -> None:
+    with LiteSVMServer(LiteSVM()) as server:
+        address = Pubkey.new_unique()
+        server.svm.airdrop(address, 1_000)
+        raw = batch_to_json([GetBalance(address, id=1), GetLatestBlockhash(id=2)])
+        resps: Any = json.loads(post(server, raw))
+        assert [resp["id"] for resp in resps] == [1, 2]
+        assert resps[0]["result"]["value"] == 1_000
+        assert server.handle("[]") is None
+
+
+def test_account_and_slot_subscriptions() -> None:
+    with LiteSVMServer(LiteSVM()) as server:
+        _, receiver, tx = transfer_tx(server, 1_000_000)
+        ws = WebSocketClient(server.ws_url)
+        ws.send(AccountSubscribe(receiver, id=1).to_json())
+        assert ws.recv() == {"jsonrpc": "2.0", "id": 1, "result": 0}
+        ws.send(SlotSubscribe(id=2).to_json())
+        assert ws.recv() == {"jsonrpc": "2.0", "id": 2, "result": 1}
+        post(server, SendVersionedTransaction(tx).to_json())
+        assert ws.recv() == {
+            "jsonrpc": "2.0",
+            "method": "accountNotification",
+            "params": {
+                "result": {
+                    "context": {"slot": 0},
+                    "value": {
+                        "lamports": 1_000_000,
+                        "data": ["", "base64"],
+                        "owner": "11111111111111111111111111111111",
+                        "executable": False,
+                        "rentEpoch": 0,
+                        "space": None,
+                    },
+                },
+                "subscription": 0,
+            },
+        }
+        server.svm.warp_to_slot(5)
+        server.notify()
+        assert ws.recv() == {
+            "jsonrpc": "2.0",
+            "method": "slotNotification",
+            "params": {
+                "result": {"slot": 5, "parent": 0, "root": 5},
+                "subscription": 1,
+            },
+        }
+        ws.close()
+
+
+def test_logs_and_signature_subscriptions() -> None:
+    with LiteSVMServer(LiteSVM()) as server:
+        _, receiver, tx = transfer_tx(server, 1_000_000)
+        ws = WebSocketClient(server.ws_url)
+        mentions = RpcTransactionLogsFilterMentions(receiver)
+        ws.send(LogsSubscribe(mentions, id=1).to_json())
+        ws.recv()
+        ws.send(SignatureSubscribe(tx.signatures[0], id=2).to_json())
+        ws.recv()
+        post(server, SendVersionedTransaction(tx).to_json())
+        logs = ws.recv()
+        assert logs["method"] == "logsNotification"
+        (logs_notification,) = parse_websocket_message(json.dumps(logs))
+        assert isinstance(logs_notification, LogsNotification)
+        assert logs_notification.result.value.signature == tx.signatures[0]
+        assert logs_notification.result.value.logs == [
+            "Program 11111111111111111111111111111111 invoke [1]",
+            "Program 11111111111111111111111111111111 success",
+        ]
+        signature = ws.recv()
+        assert signature["method"] == "signatureNotification"
+        (signature_notification,) = parse_websocket_message(json.dumps(signature))
+        assert isinstance(signature_notification, SignatureNotification)
+        assert signature_notification.result.value.err is None
+        # Subscribing to an already processed signature notifies immediately.
+        ws.send(SignatureSubscribe(tx.signatures[0], id=3).to_json())
+        assert ws.recv()["result"] == 2
+        assert ws.recv()["params"]["subscription"] == 2
+        ws.close()
+
+
+def test_program_subscription() -> None:
+    with LiteSVMServer(LiteSVM()) as server:
+        payer, receiver, tx = transfer_tx(server, 1_000_000)
+        ws = WebSocketClient(server.ws_url)
+        ws.send(ProgramSubscribe(SYSTEM_PROGRAM_ID, id=1).to_json())
+        sub_id = ws.recv()["result"]
+        post(server, SendVersionedTransaction(tx).to_json())
+        notified = []
+        for _ in range(2):
+            (notification,) = parse_websocket_message(json.dumps(ws.recv()))
+            assert isinstance(notification, ProgramNotification)
+            notified.append(notification.result.value.pubkey)
+        assert sorted(notified) == sorted([payer.pubkey(), receiver])
+        ws.send(ProgramUnsubscribe(sub_id, id=2).to_json())
+        assert ws.recv() == {"jsonrpc": "2.0", "id": 2, "result": True}
+        ws.send(ProgramUnsubscribe(sub_id, id=3).to_json())
+        assert ws.recv()["error"]["code"] == -32602
+        ws.send(GetBalance(receiver, id=4).to_json())
+        assert ws.recv()["error"]["code"] == -32601
+        ws.close()
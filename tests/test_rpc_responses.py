@@ -536,6 +536,22 @@ def test_get_block(path: str) -> None:
     assert val.parent_slot == 147078734
 
 
+def test_parsed_logs_from_block() -> None:
+    raw = (Path(__file__).parent / "data" / "get_block_json_encoding.json").read_text()
+    transactions = GetBlockResp.from_json(raw).value.transactions
+    assert transactions is not None
+    meta = transactions[0].meta
+    assert meta is not None
+    parsed_logs = meta.parsed_logs()
+    assert parsed_logs is not None
+    [invocation] = parsed_logs.invocations
+    assert invocation.program_id == Pubkey.from_string(
+        "Vote111111111111111111111111111111111111111"
+    )
+    assert invocation.depth == 1
+    assert invocation.success
+
+
 def test_get_blocks() -> None:
     raw = '{ "jsonrpc": "2.0", "result": [5, 6, 7, 8, 9, 10], "id": 1 }'
     parsed = GetBlocksResp.from_json(raw)